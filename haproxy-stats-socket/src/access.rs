@@ -0,0 +1,373 @@
+use core::{fmt, ops::Deref};
+use std::{net::SocketAddr, path::Path};
+
+use haproxy_stats::{
+    command::{CommandParseError, ServerAdminState},
+    CliError, CliLevel, Command, EnvironmentVariables, Info, ReadOnlyCommand, Statistic,
+};
+
+use crate::client::{
+    Client, ClientSendError, ClientShowCliLevelError, ClientShowEnvError, ClientShowInfoError,
+    ClientShowStatError,
+};
+
+//
+/// Client only able to read the state, e.g. for monitoring.
+///
+/// The inner `Client` is not reachable, mutating commands don't compile.
+/// The connections are lowered to the level the command needs, `user` or `operator`.
+#[derive(Debug, Clone)]
+pub struct ReadOnlyClient {
+    client: Client,
+}
+
+impl ReadOnlyClient {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    pub fn with_tcp(addr: impl Into<SocketAddr>) -> Self {
+        Self::new(Client::with_tcp(addr))
+    }
+
+    pub fn with_unix(path: impl AsRef<Path>) -> Self {
+        Self::new(Client::with_unix(path))
+    }
+
+    pub fn send(&self, command: &ReadOnlyCommand) -> Result<Vec<u8>, ClientSendError> {
+        self.client_at(command.as_command().required_level())
+            .send(command.as_command())
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn send_async(&self, command: &ReadOnlyCommand) -> Result<Vec<u8>, ClientSendError> {
+        self.client_at(command.as_command().required_level())
+            .send_async(command.as_command())
+            .await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn show_info(&self) -> Result<Info, ClientShowInfoError> {
+        self.client_at(CliLevel::User).show_info().await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn show_stat(&self) -> Result<Vec<Statistic>, ClientShowStatError> {
+        self.client_at(CliLevel::User).show_stat().await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn show_env(&self) -> Result<EnvironmentVariables, ClientShowEnvError> {
+        self.client_at(CliLevel::Operator).show_env().await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn show_env_with_name(
+        &self,
+        name: impl AsRef<str>,
+    ) -> Result<Option<Box<str>>, ClientShowEnvError> {
+        self.client_at(CliLevel::Operator)
+            .show_env_with_name(name)
+            .await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn show_cli_level(&self) -> Result<CliLevel, ClientShowCliLevelError> {
+        self.client_at(CliLevel::User).show_cli_level().await
+    }
+
+    pub fn show_info_blocking(&self) -> Result<Info, ClientShowInfoError> {
+        self.client_at(CliLevel::User).show_info_blocking()
+    }
+
+    pub fn show_stat_blocking(&self) -> Result<Vec<Statistic>, ClientShowStatError> {
+        self.client_at(CliLevel::User).show_stat_blocking()
+    }
+
+    pub fn show_env_blocking(&self) -> Result<EnvironmentVariables, ClientShowEnvError> {
+        self.client_at(CliLevel::Operator).show_env_blocking()
+    }
+
+    pub fn show_env_with_name_blocking(
+        &self,
+        name: impl AsRef<str>,
+    ) -> Result<Option<Box<str>>, ClientShowEnvError> {
+        self.client_at(CliLevel::Operator)
+            .show_env_with_name_blocking(name)
+    }
+
+    pub fn show_cli_level_blocking(&self) -> Result<CliLevel, ClientShowCliLevelError> {
+        self.client_at(CliLevel::User).show_cli_level_blocking()
+    }
+
+    // At most the level of `client`.
+    fn client_at(&self, level: CliLevel) -> Client {
+        match self.client.level() {
+            Some(x) if x <= level => self.client.clone(),
+            _ => self.client.with_level(level),
+        }
+    }
+}
+
+//
+/// Client able to change the state, obtained explicitly by `Client::admin`.
+#[derive(Debug, Clone)]
+pub struct AdminClient {
+    client: Client,
+}
+
+impl Deref for AdminClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl AdminClient {
+    #[cfg(feature = "_async")]
+    pub async fn enable_server(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin(Command::enable_server(backend, server), &[])
+            .await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn disable_server(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin(Command::disable_server(backend, server), &[])
+            .await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn set_weight(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+        weight: u32,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin(Command::set_weight(backend, server, weight), &[])
+            .await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn set_server_state(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+        state: ServerAdminState,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin(Command::set_server_state(backend, server, state), &[])
+            .await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn clear_counters(&self) -> Result<(), AdminClientError> {
+        self.send_admin(Ok(Command::clear_counters()), &[]).await
+    }
+
+    /// `args` are the server keywords, e.g. `10.0.0.3:80 weight 10`.
+    #[cfg(feature = "_async")]
+    pub async fn add_server(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+        args: impl AsRef<str>,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin(
+            Command::add_server(backend, server, args),
+            ADD_SERVER_MESSAGES,
+        )
+        .await
+    }
+
+    /// The server must be in maintenance and without sessions.
+    #[cfg(feature = "_async")]
+    pub async fn del_server(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin(Command::del_server(backend, server), DEL_SERVER_MESSAGES)
+            .await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn shutdown_sessions_server(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin(Command::shutdown_sessions_server(backend, server), &[])
+            .await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn shutdown_frontend(
+        &self,
+        frontend: impl AsRef<str>,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin(
+            Command::shutdown_frontend(frontend),
+            SHUTDOWN_FRONTEND_MESSAGES,
+        )
+        .await
+    }
+
+    pub fn enable_server_blocking(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin_blocking(Command::enable_server(backend, server), &[])
+    }
+
+    pub fn disable_server_blocking(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin_blocking(Command::disable_server(backend, server), &[])
+    }
+
+    pub fn set_weight_blocking(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+        weight: u32,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin_blocking(Command::set_weight(backend, server, weight), &[])
+    }
+
+    pub fn set_server_state_blocking(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+        state: ServerAdminState,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin_blocking(Command::set_server_state(backend, server, state), &[])
+    }
+
+    pub fn clear_counters_blocking(&self) -> Result<(), AdminClientError> {
+        self.send_admin_blocking(Ok(Command::clear_counters()), &[])
+    }
+
+    pub fn add_server_blocking(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+        args: impl AsRef<str>,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin_blocking(
+            Command::add_server(backend, server, args),
+            ADD_SERVER_MESSAGES,
+        )
+    }
+
+    pub fn del_server_blocking(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin_blocking(Command::del_server(backend, server), DEL_SERVER_MESSAGES)
+    }
+
+    pub fn shutdown_sessions_server_blocking(
+        &self,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin_blocking(Command::shutdown_sessions_server(backend, server), &[])
+    }
+
+    pub fn shutdown_frontend_blocking(
+        &self,
+        frontend: impl AsRef<str>,
+    ) -> Result<(), AdminClientError> {
+        self.send_admin_blocking(
+            Command::shutdown_frontend(frontend),
+            SHUTDOWN_FRONTEND_MESSAGES,
+        )
+    }
+
+    #[cfg(feature = "_async")]
+    async fn send_admin(
+        &self,
+        command: Result<Command, CommandParseError>,
+        messages: &[&str],
+    ) -> Result<(), AdminClientError> {
+        let command = command.map_err(AdminClientError::CommandParseError)?;
+        parse_admin_response(self.client.send_async(&command).await, messages)
+    }
+
+    fn send_admin_blocking(
+        &self,
+        command: Result<Command, CommandParseError>,
+        messages: &[&str],
+    ) -> Result<(), AdminClientError> {
+        let command = command.map_err(AdminClientError::CommandParseError)?;
+        parse_admin_response(self.client.send(&command), messages)
+    }
+}
+
+// The successful responses of `add server` and `del server`.
+const ADD_SERVER_MESSAGES: &[&str] = &["New server registered."];
+const DEL_SERVER_MESSAGES: &[&str] = &["Server deleted."];
+// Already shut down, the outcome is the same.
+const SHUTDOWN_FRONTEND_MESSAGES: &[&str] = &["Frontend was already shut down."];
+
+// These commands respond nothing on success, or one of `messages`.
+fn parse_admin_response(
+    response: Result<Vec<u8>, ClientSendError>,
+    messages: &[&str],
+) -> Result<(), AdminClientError> {
+    let response = response.map_err(AdminClientError::ClientSendError)?;
+
+    if let Some(err) = CliError::from_response_bytes(&response) {
+        return Err(AdminClientError::CliError(err));
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let response = response.trim();
+    if !response.is_empty() && !messages.contains(&response) {
+        return Err(AdminClientError::ResponseUnexpected(response.into()));
+    }
+
+    Ok(())
+}
+
+//
+impl Client {
+    pub fn read_only(&self) -> ReadOnlyClient {
+        ReadOnlyClient::new(self.clone())
+    }
+
+    pub fn admin(&self) -> AdminClient {
+        AdminClient {
+            client: self.clone(),
+        }
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum AdminClientError {
+    CommandParseError(CommandParseError),
+    ClientSendError(ClientSendError),
+    CliError(CliError),
+    ResponseUnexpected(Box<str>),
+}
+
+impl fmt::Display for AdminClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for AdminClientError {}
@@ -0,0 +1,202 @@
+use core::{fmt, str::FromStr};
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs as _},
+    path::Path,
+};
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::transport::AbstractUnixTransport;
+use crate::{
+    client::{Client, ClientBuilder},
+    master::MasterClient,
+};
+
+//
+// Length of `sun_path` without the leading NUL.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const ABSTRACT_NAME_MAX_LEN: usize = 107;
+
+//
+/// Address in the HAProxy syntax, e.g. `ipv4@127.0.0.1:9999`, `ipv6@[::1]:9999`,
+/// `unix@/run/haproxy.sock`, `abns@haproxy` or a bare path.
+///
+/// An empty, `*` or unspecified host, as in `ipv4@:9999` or `ipv4@0.0.0.0:9999`,
+/// is reached through the loopback.
+///
+/// A hostname, as in `ipv4@localhost:9999`, is resolved when parsed, to an address
+/// of the family of the prefix, IPv4 first without prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HaproxyAddress {
+    Tcp(SocketAddr),
+    Unix(Box<Path>),
+    /// `abns@` names are padded with NULs to the whole `sun_path` by HAProxy,
+    /// `abnsz@` ones (since 3.1) are not.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Abstract {
+        name: Box<str>,
+        padded: bool,
+    },
+}
+
+impl FromStr for HaproxyAddress {
+    type Err = HaproxyAddressParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(HaproxyAddressParseError::Empty);
+        }
+
+        match s.split_once('@') {
+            Some(("ipv4", addr)) => parse_socket_addr(addr, Ipv4Addr::LOCALHOST.into(), false),
+            Some(("ipv6", addr)) => parse_socket_addr(addr, Ipv6Addr::LOCALHOST.into(), false),
+            Some(("unix", path)) => Ok(Self::Unix(Path::new(path).into())),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Some(("abns", name)) => parse_abstract(name, true),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Some(("abnsz", name)) => parse_abstract(name, false),
+            // e.g. `fd@3`, `sockpair@4`
+            Some((prefix, _)) if prefix.chars().all(|x| x.is_ascii_alphanumeric()) => {
+                Err(HaproxyAddressParseError::PrefixUnsupported(prefix.into()))
+            }
+            _ if s.starts_with('/') || !s.contains(':') => Ok(Self::Unix(Path::new(s).into())),
+            _ => parse_socket_addr(s, Ipv4Addr::LOCALHOST.into(), true),
+        }
+    }
+}
+
+impl fmt::Display for HaproxyAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(SocketAddr::V4(addr)) => write!(f, "ipv4@{}", addr),
+            Self::Tcp(SocketAddr::V6(addr)) => write!(f, "ipv6@{}", addr),
+            Self::Unix(path) => write!(f, "unix@{}", path.display()),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Self::Abstract { name, padded: true } => write!(f, "abns@{}", name),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Self::Abstract {
+                name,
+                padded: false,
+            } => write!(f, "abnsz@{}", name),
+        }
+    }
+}
+
+// e.g. `127.0.0.1:9999`, `[::1]:9999`, `::1:9999`, `:9999` or `localhost:9999`,
+// the family of `default_ip` is required unless `any_family`.
+fn parse_socket_addr(
+    s: &str,
+    default_ip: IpAddr,
+    any_family: bool,
+) -> Result<HaproxyAddress, HaproxyAddressParseError> {
+    let invalid = || HaproxyAddressParseError::AddrInvalid(s.into());
+
+    let (host, port) = s.rsplit_once(':').ok_or_else(invalid)?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let port = port.parse().map_err(|_| invalid())?;
+
+    let ip = match host {
+        "" | "*" => default_ip,
+        host => match host.parse::<IpAddr>() {
+            Ok(ip) if ip.is_unspecified() => default_ip,
+            Ok(ip) => ip,
+            Err(_) if is_hostname(host) => resolve_host(host, port, default_ip, any_family)?,
+            Err(_) => return Err(invalid()),
+        },
+    };
+
+    Ok(HaproxyAddress::Tcp(SocketAddr::new(ip, port)))
+}
+
+fn is_hostname(s: &str) -> bool {
+    s.split('.').all(|label| {
+        !label.is_empty() && label.chars().all(|x| x.is_ascii_alphanumeric() || x == '-')
+    })
+}
+
+fn resolve_host(
+    host: &str,
+    port: u16,
+    default_ip: IpAddr,
+    any_family: bool,
+) -> Result<IpAddr, HaproxyAddressParseError> {
+    let unresolved = || HaproxyAddressParseError::HostUnresolved(host.into());
+
+    let ips = (host, port)
+        .to_socket_addrs()
+        .map_err(|_| unresolved())?
+        .map(|x| x.ip())
+        .collect::<Vec<_>>();
+    ips.iter()
+        .find(|x| x.is_ipv4() == default_ip.is_ipv4())
+        .or_else(|| ips.first().filter(|_| any_family))
+        .copied()
+        .ok_or_else(unresolved)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn parse_abstract(name: &str, padded: bool) -> Result<HaproxyAddress, HaproxyAddressParseError> {
+    if name.len() > ABSTRACT_NAME_MAX_LEN {
+        return Err(HaproxyAddressParseError::NameTooLong);
+    }
+    Ok(HaproxyAddress::Abstract {
+        name: name.into(),
+        padded,
+    })
+}
+
+//
+#[derive(Debug)]
+pub enum HaproxyAddressParseError {
+    Empty,
+    PrefixUnsupported(Box<str>),
+    AddrInvalid(Box<str>),
+    /// Not resolved to an address of the family of the prefix.
+    HostUnresolved(Box<str>),
+    NameTooLong,
+}
+
+impl fmt::Display for HaproxyAddressParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for HaproxyAddressParseError {}
+
+//
+impl ClientBuilder {
+    /// See `HaproxyAddress`.
+    pub fn from_haproxy_address(s: &str) -> Result<Self, HaproxyAddressParseError> {
+        Ok(Self::with_haproxy_address(s.parse()?))
+    }
+
+    pub fn with_haproxy_address(address: HaproxyAddress) -> Self {
+        match address {
+            HaproxyAddress::Tcp(addr) => Self::with_tcp(addr),
+            HaproxyAddress::Unix(path) => Self::with_unix(path),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            HaproxyAddress::Abstract { name, padded } => {
+                let mut name = name.into_boxed_bytes().into_vec();
+                if padded {
+                    name.resize(ABSTRACT_NAME_MAX_LEN, 0);
+                }
+                Self::with_transport(AbstractUnixTransport(name.into()))
+            }
+        }
+    }
+}
+
+impl Client {
+    /// See `HaproxyAddress`.
+    pub fn from_haproxy_address(s: &str) -> Result<Self, HaproxyAddressParseError> {
+        Ok(ClientBuilder::from_haproxy_address(s)?.build())
+    }
+}
+
+impl MasterClient {
+    /// See `HaproxyAddress`, e.g. the address of `-S`.
+    pub fn from_haproxy_address(s: &str) -> Result<Self, HaproxyAddressParseError> {
+        Ok(Self::new(Client::from_haproxy_address(s)?))
+    }
+}
@@ -0,0 +1,196 @@
+use core::time::Duration;
+use std::{net::SocketAddr, path::Path, sync::Arc, time::Instant};
+
+use haproxy_stats::{CliLevel, CliMode, Command};
+
+#[cfg(feature = "_async")]
+use super::AsyncRuntime;
+use super::{io::Deadline, Client, ClientSendError, RetryPolicy};
+use crate::{
+    master::ProcessTarget,
+    transport::{TcpTransport, Transport, UnixTransport},
+};
+
+//
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClientOptions {
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) read_timeout: Option<Duration>,
+    pub(crate) timeout: Option<Duration>,
+    // Shared by every send and retry, e.g. the timeout of a `ClientSet` node.
+    pub(crate) deadline: Option<Instant>,
+    pub(crate) max_response_size: Option<usize>,
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    pub(crate) target: Option<ProcessTarget>,
+    pub(crate) level: Option<CliLevel>,
+    pub(crate) modes: Vec<CliMode>,
+    #[cfg(feature = "_async")]
+    pub(crate) runtime: AsyncRuntime,
+}
+
+impl ClientOptions {
+    /// The deadline of one send, `timeout` from now or the shared one if sooner.
+    pub(crate) fn new_deadline(&self) -> Deadline {
+        Deadline::new(self.timeout).min(self.deadline)
+    }
+
+    /// The time left before the shared deadline.
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|x| x.saturating_duration_since(Instant::now()))
+    }
+
+    /// Prefixed with the target on the master CLI, e.g. `@1 show stat`.
+    pub(crate) fn to_write_bytes(&self, command: &Command) -> Vec<u8> {
+        match &self.target {
+            Some(target) => [
+                format!("{} ", target).into_bytes(),
+                command.to_write_bytes(),
+            ]
+            .concat(),
+            None => command.to_write_bytes(),
+        }
+    }
+
+    /// The modes are only available at the admin level.
+    fn required_level(&self, command: &Command) -> CliLevel {
+        if self.modes.is_empty() {
+            command.required_level()
+        } else {
+            CliLevel::Admin
+        }
+    }
+
+    /// Refuses locally the commands above the declared level.
+    pub(crate) fn check_level(&self, command: &Command) -> Result<(), ClientSendError> {
+        let required = self.required_level(command);
+        match self.level {
+            Some(level) if required > level => Err(ClientSendError::LevelInsufficient {
+                command: command.as_str().into(),
+                required,
+                level,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Commands run in interactive mode before the commands, on every new connection,
+    /// turning on the modes, then lowering the level.
+    pub(crate) fn setup_commands<'a>(
+        &self,
+        commands: impl IntoIterator<Item = &'a Command>,
+    ) -> Vec<Command> {
+        let mut modes = self.modes.clone();
+        for command in commands {
+            modes.extend(command.required_modes());
+        }
+        modes.sort();
+        modes.dedup();
+
+        modes
+            .iter()
+            .map(|x| x.to_command())
+            .chain(self.level.and_then(|x| x.to_command()))
+            .collect()
+    }
+}
+
+//
+#[derive(Debug, Clone)]
+pub struct ClientBuilder {
+    transport: Arc<dyn Transport>,
+    options: ClientOptions,
+}
+
+impl ClientBuilder {
+    pub fn with_tcp(addr: impl Into<SocketAddr>) -> Self {
+        Self::with_transport(TcpTransport(addr.into()))
+    }
+
+    pub fn with_unix(path: impl AsRef<Path>) -> Self {
+        Self::with_transport(UnixTransport(path.as_ref().into()))
+    }
+
+    pub fn with_transport(transport: impl Transport) -> Self {
+        Self {
+            transport: Arc::new(transport),
+            options: Default::default(),
+        }
+    }
+
+    /// Fails with `ClientSendError::ConnectTimeout`.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.options.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Applies to every read, fails with `ClientSendError::ReadTimeout`.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.options.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Applies to a whole send, from connecting to the end of the response,
+    /// fails with `ClientSendError::Timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// Fails with `ClientSendError::ResponseTooLarge`.
+    pub fn max_response_size(mut self, size: usize) -> Self {
+        self.options.max_response_size = Some(size);
+        self
+    }
+
+    /// Every attempt has its own `timeout`.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.options.retry_policy = Some(policy);
+        self
+    }
+
+    /// Every connection is lowered to the level, e.g. `CliLevel::User` for read-only tooling,
+    /// commands above it fail locally with `ClientSendError::LevelInsufficient`.
+    pub fn level(mut self, level: CliLevel) -> Self {
+        self.options.level = Some(level);
+        self
+    }
+
+    /// Turned on before every command, for the commands not known to need it,
+    /// see `Command::require_mode`.
+    pub fn mode(mut self, mode: CliMode) -> Self {
+        if !self.options.modes.contains(&mode) {
+            self.options.modes.push(mode);
+        }
+        self
+    }
+
+    /// Used by the async methods, see `AsyncRuntime::default`.
+    #[cfg(feature = "_async")]
+    pub fn async_runtime(mut self, runtime: AsyncRuntime) -> Self {
+        self.options.runtime = runtime;
+        self
+    }
+
+    pub fn build(self) -> Client {
+        Client {
+            transport: self.transport,
+            options: self.options,
+        }
+    }
+}
+
+//
+impl Client {
+    pub fn builder_with_tcp(addr: impl Into<SocketAddr>) -> ClientBuilder {
+        ClientBuilder::with_tcp(addr)
+    }
+
+    pub fn builder_with_unix(path: impl AsRef<Path>) -> ClientBuilder {
+        ClientBuilder::with_unix(path)
+    }
+
+    pub fn builder_with_transport(transport: impl Transport) -> ClientBuilder {
+        ClientBuilder::with_transport(transport)
+    }
+}
@@ -0,0 +1,76 @@
+use core::fmt;
+
+use haproxy_stats::{level::CliLevelParseError, CliError, CliLevel, Command};
+
+use super::{Client, ClientSendError};
+#[cfg(feature = "_async")]
+use crate::session::Session;
+use crate::trace::Step;
+
+//
+impl Client {
+    /// The level after lowering, if it was declared.
+    #[cfg(feature = "_async")]
+    pub async fn show_cli_level(&self) -> Result<CliLevel, ClientShowCliLevelError> {
+        parse_show_cli_level(self.send_async(&Command::show_cli_level()).await)
+    }
+
+    /// Declares the level returned by `show cli level`, so that commands above it fail locally.
+    #[cfg(feature = "_async")]
+    pub async fn detect_level(&self) -> Result<Self, ClientShowCliLevelError> {
+        let level = self.show_cli_level().await?;
+        Ok(self.with_level(level))
+    }
+
+    /// The level after lowering, if it was declared.
+    pub fn show_cli_level_blocking(&self) -> Result<CliLevel, ClientShowCliLevelError> {
+        parse_show_cli_level(self.send(&Command::show_cli_level()))
+    }
+
+    /// Declares the level returned by `show cli level`, so that commands above it fail locally.
+    pub fn detect_level_blocking(&self) -> Result<Self, ClientShowCliLevelError> {
+        let level = self.show_cli_level_blocking()?;
+        Ok(self.with_level(level))
+    }
+}
+
+#[cfg(feature = "_async")]
+impl Session {
+    pub async fn show_cli_level(&mut self) -> Result<CliLevel, ClientShowCliLevelError> {
+        parse_show_cli_level(self.send(&Command::show_cli_level()).await)
+    }
+}
+
+fn parse_show_cli_level(
+    response: Result<Vec<u8>, ClientSendError>,
+) -> Result<CliLevel, ClientShowCliLevelError> {
+    let response = response.map_err(ClientShowCliLevelError::ClientSendError)?;
+
+    if let Some(err) = CliError::from_response_bytes(&response) {
+        return Err(ClientShowCliLevelError::CliError(err));
+    }
+
+    let step = Step::start("parse");
+    let len = response.len();
+    let level = CliLevel::from_response_bytes(response);
+    step.finish_parse(&level, len);
+    let level = level.map_err(ClientShowCliLevelError::ResponseParseError)?;
+
+    Ok(level)
+}
+
+//
+#[derive(Debug)]
+pub enum ClientShowCliLevelError {
+    ClientSendError(ClientSendError),
+    CliError(CliError),
+    ResponseParseError(CliLevelParseError),
+}
+
+impl fmt::Display for ClientShowCliLevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for ClientShowCliLevelError {}
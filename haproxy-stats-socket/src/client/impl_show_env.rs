@@ -1,28 +1,134 @@
 use core::fmt;
 
-use haproxy_stats::{env::EnvironmentVariablesFromKvBytesError, Command, EnvironmentVariables};
+use haproxy_stats::{
+    command::CommandParseError, env::EnvironmentVariablesFromKvBytesError, CliError, Command,
+    EnvironmentVariables,
+};
 
 use super::{Client, ClientSendError};
+#[cfg(feature = "_async")]
+use crate::pool::Pool;
+#[cfg(feature = "_async")]
+use crate::session::Session;
+use crate::trace::Step;
+
+//
+const VARIABLE_NOT_FOUND: &[u8] = b"Variable not found";
 
 //
 impl Client {
+    #[cfg(feature = "_async")]
     pub async fn show_env(&self) -> Result<EnvironmentVariables, ClientShowEnvError> {
-        let response = self
-            .send_async(&Command::show_env())
-            .await
-            .map_err(ClientShowEnvError::ClientSendError)?;
+        parse_show_env(self.send_async(&Command::show_env()).await)
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn show_env_with_name(
+        &self,
+        name: impl AsRef<str>,
+    ) -> Result<Option<Box<str>>, ClientShowEnvError> {
+        let name = name.as_ref();
+        let command = show_env_with_name_command(name)?;
+        parse_show_env_with_name(self.send_async(&command).await, name)
+    }
 
-        let info = EnvironmentVariables::from_kv_bytes(response)
-            .map_err(ClientShowEnvError::ResponseParseError)?;
+    pub fn show_env_blocking(&self) -> Result<EnvironmentVariables, ClientShowEnvError> {
+        parse_show_env(self.send(&Command::show_env()))
+    }
+
+    pub fn show_env_with_name_blocking(
+        &self,
+        name: impl AsRef<str>,
+    ) -> Result<Option<Box<str>>, ClientShowEnvError> {
+        let name = name.as_ref();
+        let command = show_env_with_name_command(name)?;
+        parse_show_env_with_name(self.send(&command), name)
+    }
+}
+
+#[cfg(feature = "_async")]
+impl Session {
+    pub async fn show_env(&mut self) -> Result<EnvironmentVariables, ClientShowEnvError> {
+        parse_show_env(self.send(&Command::show_env()).await)
+    }
+
+    pub async fn show_env_with_name(
+        &mut self,
+        name: impl AsRef<str>,
+    ) -> Result<Option<Box<str>>, ClientShowEnvError> {
+        let name = name.as_ref();
+        let command = show_env_with_name_command(name)?;
+        parse_show_env_with_name(self.send(&command).await, name)
+    }
+}
+
+#[cfg(feature = "_async")]
+impl Pool {
+    pub async fn show_env(&self) -> Result<EnvironmentVariables, ClientShowEnvError> {
+        parse_show_env(self.send(&Command::show_env()).await)
+    }
 
-        Ok(info)
+    pub async fn show_env_with_name(
+        &self,
+        name: impl AsRef<str>,
+    ) -> Result<Option<Box<str>>, ClientShowEnvError> {
+        let name = name.as_ref();
+        let command = show_env_with_name_command(name)?;
+        parse_show_env_with_name(self.send(&command).await, name)
     }
 }
 
+fn show_env_with_name_command(name: &str) -> Result<Command, ClientShowEnvError> {
+    Command::show_env_with_name(name).map_err(ClientShowEnvError::CommandParseError)
+}
+
+fn parse_show_env(
+    response: Result<Vec<u8>, ClientSendError>,
+) -> Result<EnvironmentVariables, ClientShowEnvError> {
+    let response = response.map_err(ClientShowEnvError::ClientSendError)?;
+
+    if let Some(err) = CliError::from_response_bytes(&response) {
+        return Err(ClientShowEnvError::CliError(err));
+    }
+
+    let step = Step::start("parse");
+    let len = response.len();
+    let vars = EnvironmentVariables::from_kv_bytes(response);
+    step.finish_parse(&vars, len);
+    let vars = vars.map_err(ClientShowEnvError::ResponseParseError)?;
+
+    Ok(vars)
+}
+
+fn parse_show_env_with_name(
+    response: Result<Vec<u8>, ClientSendError>,
+    name: &str,
+) -> Result<Option<Box<str>>, ClientShowEnvError> {
+    let response = response.map_err(ClientShowEnvError::ClientSendError)?;
+
+    if let Some(err) = CliError::from_response_bytes(&response) {
+        return Err(ClientShowEnvError::CliError(err));
+    }
+
+    if response.starts_with(VARIABLE_NOT_FOUND) {
+        return Ok(None);
+    }
+
+    let step = Step::start("parse");
+    let len = response.len();
+    let vars = EnvironmentVariables::from_kv_bytes(response);
+    step.finish_parse(&vars, len);
+    let vars = vars.map_err(ClientShowEnvError::ResponseParseError)?;
+
+    Ok(vars.get(name).cloned())
+}
+
 //
 #[derive(Debug)]
 pub enum ClientShowEnvError {
+    CommandParseError(CommandParseError),
     ClientSendError(ClientSendError),
+    CliError(CliError),
     ResponseParseError(EnvironmentVariablesFromKvBytesError),
 }
 
@@ -1,28 +1,63 @@
 use core::fmt;
 
-use haproxy_stats::{info::InfoFromKvBytesError, Command, Info};
+use haproxy_stats::{info::InfoFromKvBytesError, CliError, Command, Info};
 
 use super::{Client, ClientSendError};
+#[cfg(feature = "_async")]
+use crate::pool::Pool;
+#[cfg(feature = "_async")]
+use crate::session::Session;
+use crate::trace::Step;
 
 //
 impl Client {
+    #[cfg(feature = "_async")]
     pub async fn show_info(&self) -> Result<Info, ClientShowInfoError> {
-        let response = self
-            .send_async(&Command::show_info())
-            .await
-            .map_err(ClientShowInfoError::ClientSendError)?;
+        parse_show_info(self.send_async(&Command::show_info()).await)
+    }
 
-        let info =
-            Info::from_kv_bytes(response).map_err(ClientShowInfoError::ResponseParseError)?;
+    pub fn show_info_blocking(&self) -> Result<Info, ClientShowInfoError> {
+        parse_show_info(self.send(&Command::show_info()))
+    }
+}
 
-        Ok(info)
+#[cfg(feature = "_async")]
+impl Session {
+    pub async fn show_info(&mut self) -> Result<Info, ClientShowInfoError> {
+        parse_show_info(self.send(&Command::show_info()).await)
+    }
+}
+
+#[cfg(feature = "_async")]
+impl Pool {
+    pub async fn show_info(&self) -> Result<Info, ClientShowInfoError> {
+        parse_show_info(self.send(&Command::show_info()).await)
     }
 }
 
+fn parse_show_info(
+    response: Result<Vec<u8>, ClientSendError>,
+) -> Result<Info, ClientShowInfoError> {
+    let response = response.map_err(ClientShowInfoError::ClientSendError)?;
+
+    if let Some(err) = CliError::from_response_bytes(&response) {
+        return Err(ClientShowInfoError::CliError(err));
+    }
+
+    let step = Step::start("parse");
+    let len = response.len();
+    let info = Info::from_kv_bytes(response);
+    step.finish_parse(&info, len);
+    let info = info.map_err(ClientShowInfoError::ResponseParseError)?;
+
+    Ok(info)
+}
+
 //
 #[derive(Debug)]
 pub enum ClientShowInfoError {
     ClientSendError(ClientSendError),
+    CliError(CliError),
     ResponseParseError(InfoFromKvBytesError),
 }
 
@@ -0,0 +1,75 @@
+use core::fmt;
+
+use haproxy_stats::{proc::ProcessesFromTableBytesError, CliError, Command, Processes};
+
+use super::{Client, ClientSendError};
+#[cfg(feature = "_async")]
+use crate::session::Session;
+use crate::{master::MasterClient, trace::Step};
+
+//
+impl Client {
+    /// Only on the master CLI.
+    #[cfg(feature = "_async")]
+    pub async fn show_proc(&self) -> Result<Processes, ClientShowProcError> {
+        parse_show_proc(self.send_async(&Command::show_proc()).await)
+    }
+
+    /// Only on the master CLI.
+    pub fn show_proc_blocking(&self) -> Result<Processes, ClientShowProcError> {
+        parse_show_proc(self.send(&Command::show_proc()))
+    }
+}
+
+#[cfg(feature = "_async")]
+impl Session {
+    /// Only on the master CLI.
+    pub async fn show_proc(&mut self) -> Result<Processes, ClientShowProcError> {
+        parse_show_proc(self.send(&Command::show_proc()).await)
+    }
+}
+
+impl MasterClient {
+    #[cfg(feature = "_async")]
+    pub async fn show_proc(&self) -> Result<Processes, ClientShowProcError> {
+        self.client().show_proc().await
+    }
+
+    pub fn show_proc_blocking(&self) -> Result<Processes, ClientShowProcError> {
+        self.client().show_proc_blocking()
+    }
+}
+
+fn parse_show_proc(
+    response: Result<Vec<u8>, ClientSendError>,
+) -> Result<Processes, ClientShowProcError> {
+    let response = response.map_err(ClientShowProcError::ClientSendError)?;
+
+    if let Some(err) = CliError::from_response_bytes(&response) {
+        return Err(ClientShowProcError::CliError(err));
+    }
+
+    let step = Step::start("parse");
+    let len = response.len();
+    let processes = Processes::from_table_bytes(response);
+    step.finish_parse(&processes, len);
+    let processes = processes.map_err(ClientShowProcError::ResponseParseError)?;
+
+    Ok(processes)
+}
+
+//
+#[derive(Debug)]
+pub enum ClientShowProcError {
+    ClientSendError(ClientSendError),
+    CliError(CliError),
+    ResponseParseError(ProcessesFromTableBytesError),
+}
+
+impl fmt::Display for ClientShowProcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for ClientShowProcError {}
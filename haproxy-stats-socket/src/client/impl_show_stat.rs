@@ -1,28 +1,63 @@
 use core::fmt;
 
-use haproxy_stats::{stat::StatisticsFromCsvBytesError, Command, Statistic, Statistics};
+use haproxy_stats::{stat::StatisticsFromCsvBytesError, CliError, Command, Statistic, Statistics};
 
 use super::{Client, ClientSendError};
+#[cfg(feature = "_async")]
+use crate::pool::Pool;
+#[cfg(feature = "_async")]
+use crate::session::Session;
+use crate::trace::Step;
 
 //
 impl Client {
+    #[cfg(feature = "_async")]
     pub async fn show_stat(&self) -> Result<Vec<Statistic>, ClientShowStatError> {
-        let response = self
-            .send_async(&Command::show_stat())
-            .await
-            .map_err(ClientShowStatError::ClientSendError)?;
+        parse_show_stat(self.send_async(&Command::show_stat()).await)
+    }
 
-        let statistics = Statistics::from_csv_bytes(response)
-            .map_err(ClientShowStatError::ResponseParseError)?;
+    pub fn show_stat_blocking(&self) -> Result<Vec<Statistic>, ClientShowStatError> {
+        parse_show_stat(self.send(&Command::show_stat()))
+    }
+}
 
-        Ok(statistics.0)
+#[cfg(feature = "_async")]
+impl Session {
+    pub async fn show_stat(&mut self) -> Result<Vec<Statistic>, ClientShowStatError> {
+        parse_show_stat(self.send(&Command::show_stat()).await)
+    }
+}
+
+#[cfg(feature = "_async")]
+impl Pool {
+    pub async fn show_stat(&self) -> Result<Vec<Statistic>, ClientShowStatError> {
+        parse_show_stat(self.send(&Command::show_stat()).await)
     }
 }
 
+fn parse_show_stat(
+    response: Result<Vec<u8>, ClientSendError>,
+) -> Result<Vec<Statistic>, ClientShowStatError> {
+    let response = response.map_err(ClientShowStatError::ClientSendError)?;
+
+    if let Some(err) = CliError::from_response_bytes(&response) {
+        return Err(ClientShowStatError::CliError(err));
+    }
+
+    let step = Step::start("parse");
+    let len = response.len();
+    let statistics = Statistics::from_csv_bytes(response);
+    step.finish_parse(&statistics, len);
+    let statistics = statistics.map_err(ClientShowStatError::ResponseParseError)?;
+
+    Ok(statistics.0)
+}
+
 //
 #[derive(Debug)]
 pub enum ClientShowStatError {
     ClientSendError(ClientSendError),
+    CliError(CliError),
     ResponseParseError(StatisticsFromCsvBytesError),
 }
 
@@ -0,0 +1,124 @@
+use core::{fmt, time::Duration};
+
+use haproxy_stats::{
+    command::CommandParseError, wait::WaitStatusFromWaitBytesError, CliError, Command, WaitStatus,
+};
+
+use super::{Client, ClientSendError};
+#[cfg(feature = "_async")]
+use crate::pool::Pool;
+#[cfg(feature = "_async")]
+use crate::session::Session;
+use crate::trace::Step;
+
+//
+impl Client {
+    /// Since 2.7, the timeouts of the client have to be longer than the delay.
+    #[cfg(feature = "_async")]
+    pub async fn wait(&self, delay: Duration) -> Result<WaitStatus, ClientWaitError> {
+        parse_wait(self.send_async(&Command::wait(delay)).await)
+    }
+
+    /// Until the server can be deleted, e.g. before `del server`.
+    #[cfg(feature = "_async")]
+    pub async fn wait_srv_removable(
+        &self,
+        delay: Duration,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+    ) -> Result<WaitStatus, ClientWaitError> {
+        let command = wait_srv_removable_command(delay, backend, server)?;
+        parse_wait(self.send_async(&command).await)
+    }
+
+    /// Since 2.7, the timeouts of the client have to be longer than the delay.
+    pub fn wait_blocking(&self, delay: Duration) -> Result<WaitStatus, ClientWaitError> {
+        parse_wait(self.send(&Command::wait(delay)))
+    }
+
+    /// Until the server can be deleted, e.g. before `del server`.
+    pub fn wait_srv_removable_blocking(
+        &self,
+        delay: Duration,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+    ) -> Result<WaitStatus, ClientWaitError> {
+        let command = wait_srv_removable_command(delay, backend, server)?;
+        parse_wait(self.send(&command))
+    }
+}
+
+#[cfg(feature = "_async")]
+impl Session {
+    pub async fn wait(&mut self, delay: Duration) -> Result<WaitStatus, ClientWaitError> {
+        parse_wait(self.send(&Command::wait(delay)).await)
+    }
+
+    pub async fn wait_srv_removable(
+        &mut self,
+        delay: Duration,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+    ) -> Result<WaitStatus, ClientWaitError> {
+        let command = wait_srv_removable_command(delay, backend, server)?;
+        parse_wait(self.send(&command).await)
+    }
+}
+
+#[cfg(feature = "_async")]
+impl Pool {
+    pub async fn wait(&self, delay: Duration) -> Result<WaitStatus, ClientWaitError> {
+        parse_wait(self.send(&Command::wait(delay)).await)
+    }
+
+    pub async fn wait_srv_removable(
+        &self,
+        delay: Duration,
+        backend: impl AsRef<str>,
+        server: impl AsRef<str>,
+    ) -> Result<WaitStatus, ClientWaitError> {
+        let command = wait_srv_removable_command(delay, backend, server)?;
+        parse_wait(self.send(&command).await)
+    }
+}
+
+fn wait_srv_removable_command(
+    delay: Duration,
+    backend: impl AsRef<str>,
+    server: impl AsRef<str>,
+) -> Result<Command, ClientWaitError> {
+    Command::wait_srv_removable(delay, backend, server).map_err(ClientWaitError::CommandParseError)
+}
+
+fn parse_wait(response: Result<Vec<u8>, ClientSendError>) -> Result<WaitStatus, ClientWaitError> {
+    let response = response.map_err(ClientWaitError::ClientSendError)?;
+
+    if let Some(err) = CliError::from_response_bytes(&response) {
+        return Err(ClientWaitError::CliError(err));
+    }
+
+    let step = Step::start("parse");
+    let len = response.len();
+    let status = WaitStatus::from_wait_bytes(response);
+    step.finish_parse(&status, len);
+    let status = status.map_err(ClientWaitError::ResponseParseError)?;
+
+    Ok(status)
+}
+
+//
+#[derive(Debug)]
+pub enum ClientWaitError {
+    CommandParseError(CommandParseError),
+    ClientSendError(ClientSendError),
+    CliError(CliError),
+    ResponseParseError(WaitStatusFromWaitBytesError),
+}
+
+impl fmt::Display for ClientWaitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for ClientWaitError {}
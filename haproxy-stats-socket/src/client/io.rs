@@ -0,0 +1,590 @@
+#[cfg(feature = "_async")]
+use core::future::Future;
+use core::time::Duration;
+use std::{
+    io::{Error as IoError, ErrorKind as IoErrorKind},
+    time::Instant,
+};
+
+#[cfg(feature = "_async")]
+use super::runtime::{self, AsyncRuntime};
+use super::{builder::ClientOptions, prompt::Prompt, ClientSendError};
+#[cfg(feature = "_async")]
+pub(crate) use crate::transport::AsyncStream;
+use crate::{
+    trace::Step,
+    transport::{BlockingStream, Transport},
+};
+
+//
+const BUF_SIZE: usize = 2048;
+
+//
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Deadline(Option<Instant>);
+
+impl Deadline {
+    pub(crate) fn new(timeout: Option<Duration>) -> Self {
+        Self(timeout.map(|x| Instant::now() + x))
+    }
+
+    pub(crate) fn min(self, at: Option<Instant>) -> Self {
+        match (self.0, at) {
+            (Some(x), Some(at)) => Self(Some(x.min(at))),
+            (x, at) => Self(x.or(at)),
+        }
+    }
+
+    /// Picks the shorter one of the timeout and the remaining time.
+    fn pick(
+        &self,
+        timeout: Option<Duration>,
+        timeout_kind: TimeoutKind,
+    ) -> Result<Option<(Duration, TimeoutKind)>, ClientSendError> {
+        let remaining = match self.0 {
+            Some(at) => {
+                let remaining = at.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Err(ClientSendError::Timeout);
+                }
+                Some(remaining)
+            }
+            None => None,
+        };
+
+        Ok(match (timeout, remaining) {
+            (Some(timeout), Some(remaining)) if remaining <= timeout => {
+                Some((remaining, TimeoutKind::Total))
+            }
+            (Some(timeout), _) => Some((timeout, timeout_kind)),
+            (None, Some(remaining)) => Some((remaining, TimeoutKind::Total)),
+            (None, None) => None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TimeoutKind {
+    Connect,
+    Read,
+    Total,
+}
+
+impl TimeoutKind {
+    fn to_error(self) -> ClientSendError {
+        match self {
+            Self::Connect => ClientSendError::ConnectTimeout,
+            Self::Read => ClientSendError::ReadTimeout,
+            Self::Total => ClientSendError::Timeout,
+        }
+    }
+}
+
+fn is_timed_out(err: &IoError) -> bool {
+    matches!(err.kind(), IoErrorKind::WouldBlock | IoErrorKind::TimedOut)
+}
+
+#[cfg(feature = "_async")]
+async fn with_timeout<T>(
+    runtime: AsyncRuntime,
+    future: impl Future<Output = Result<T, ClientSendError>>,
+    timeout: Option<(Duration, TimeoutKind)>,
+) -> Result<T, ClientSendError> {
+    match timeout {
+        Some((duration, kind)) => runtime
+            .timeout(duration, future)
+            .await
+            .ok_or_else(|| kind.to_error())?,
+        None => future.await,
+    }
+}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReadUntil<'a> {
+    Eof,
+    Prompt(&'a Prompt),
+}
+
+pub(crate) fn unexpected_eof() -> ClientSendError {
+    ClientSendError::ReadFailed(IoError::new(
+        IoErrorKind::UnexpectedEof,
+        "connection closed before prompt",
+    ))
+}
+
+fn check_response_size(response: &[u8], options: &ClientOptions) -> Result<(), ClientSendError> {
+    match options.max_response_size {
+        Some(max) if response.len() > max => Err(ClientSendError::ResponseTooLarge),
+        _ => Ok(()),
+    }
+}
+
+//
+pub(crate) fn connect(
+    transport: &dyn Transport,
+    options: &ClientOptions,
+    deadline: Deadline,
+) -> Result<BlockingStream, ClientSendError> {
+    let step = Step::start("connect");
+    let ret = deadline
+        .pick(options.connect_timeout, TimeoutKind::Connect)
+        .and_then(|timeout| {
+            transport
+                .connect(timeout.map(|x| x.0))
+                .map_err(|err| match timeout {
+                    Some((_, kind)) if is_timed_out(&err) => kind.to_error(),
+                    _ => ClientSendError::ConnectFailed(err),
+                })
+        });
+    step.finish(&ret, |_| None);
+    ret
+}
+
+pub(crate) fn write(
+    stream: &mut BlockingStream,
+    bytes: &[u8],
+    deadline: Deadline,
+) -> Result<(), ClientSendError> {
+    let step = Step::start("write");
+    let ret = deadline.pick(None, TimeoutKind::Total).and_then(|timeout| {
+        stream
+            .set_write_timeout(timeout.map(|x| x.0))
+            .map_err(ClientSendError::WriteFailed)?;
+
+        stream.write_all(bytes).map_err(|err| {
+            if is_timed_out(&err) && timeout.is_some() {
+                ClientSendError::Timeout
+            } else {
+                ClientSendError::WriteFailed(err)
+            }
+        })
+    });
+    step.finish(&ret, |_| Some(bytes.len()));
+    ret
+}
+
+/// `None` if the connection was closed before any byte was received,
+/// only for `ReadUntil::Prompt`, e.g. the CLI timeout expired.
+pub(crate) fn read(
+    stream: &mut BlockingStream,
+    until: ReadUntil<'_>,
+    options: &ClientOptions,
+    deadline: Deadline,
+) -> Result<Option<Vec<u8>>, ClientSendError> {
+    let step = Step::start("read");
+    let mut response = Vec::with_capacity(BUF_SIZE);
+    let ret = read_with(stream, options, deadline, |bytes| {
+        on_read(&mut response, bytes, until, options)
+    });
+    step.finish(&ret, |x| x.as_ref().map(Vec::len));
+    ret
+}
+
+/// Reads the response to `prompt`, which is only the prompt of the session.
+pub(crate) fn read_prompt(
+    stream: &mut BlockingStream,
+    options: &ClientOptions,
+    deadline: Deadline,
+) -> Result<Prompt, ClientSendError> {
+    let step = Step::start("read");
+    let mut response = vec![];
+    let ret = read_with(stream, options, deadline, |bytes| {
+        on_read_prompt(&mut response, bytes, options)
+    });
+    step.finish(&ret, |_| None);
+    ret
+}
+
+/// Reads the responses of `count` pipelined commands, every one is delimited by the prompt.
+pub(crate) fn read_prompts(
+    stream: &mut BlockingStream,
+    prompt: &Prompt,
+    count: usize,
+    options: &ClientOptions,
+    deadline: Deadline,
+) -> Result<Vec<Vec<u8>>, ClientSendError> {
+    if count == 0 {
+        return Ok(vec![]);
+    }
+
+    let step = Step::start("read");
+    let mut responses = PromptResponses::new(prompt, count);
+    let ret = read_with(stream, options, deadline, |bytes| {
+        responses.on_read(bytes, options)
+    });
+    step.finish(&ret, |x| Some(x.iter().map(Vec::len).sum()));
+    ret
+}
+
+// Reads until `on_read` returns a value, it is called with the bytes of every read, empty at EOF.
+fn read_with<T>(
+    stream: &mut BlockingStream,
+    options: &ClientOptions,
+    deadline: Deadline,
+    mut on_read: impl FnMut(&[u8]) -> Result<Option<T>, ClientSendError>,
+) -> Result<T, ClientSendError> {
+    let mut buf = vec![0; BUF_SIZE];
+    loop {
+        let timeout = deadline.pick(options.read_timeout, TimeoutKind::Read)?;
+        stream
+            .set_read_timeout(timeout.map(|x| x.0))
+            .map_err(ClientSendError::ReadFailed)?;
+
+        let n = stream.read(&mut buf).map_err(|err| match timeout {
+            Some((_, kind)) if is_timed_out(&err) => kind.to_error(),
+            _ => ClientSendError::ReadFailed(err),
+        })?;
+
+        if let Some(ret) = on_read(&buf[..n])? {
+            return Ok(ret);
+        }
+    }
+}
+
+//
+#[cfg(feature = "_async")]
+pub(crate) async fn connect_async(
+    transport: &dyn Transport,
+    options: &ClientOptions,
+    deadline: Deadline,
+) -> Result<AsyncStream, ClientSendError> {
+    let step = Step::start("connect");
+    let ret = async {
+        let timeout = deadline.pick(options.connect_timeout, TimeoutKind::Connect)?;
+        with_timeout(
+            options.runtime,
+            async {
+                transport
+                    .connect_async(options.runtime)
+                    .await
+                    .map_err(ClientSendError::ConnectFailed)
+            },
+            timeout,
+        )
+        .await
+    }
+    .await;
+    step.finish(&ret, |_| None);
+    ret
+}
+
+#[cfg(feature = "_async")]
+pub(crate) async fn write_async(
+    stream: &mut AsyncStream,
+    bytes: &[u8],
+    options: &ClientOptions,
+    deadline: Deadline,
+) -> Result<(), ClientSendError> {
+    let step = Step::start("write");
+    let ret = async {
+        let timeout = deadline.pick(None, TimeoutKind::Total)?;
+        with_timeout(
+            options.runtime,
+            async {
+                runtime::write_all(stream, bytes)
+                    .await
+                    .map_err(ClientSendError::WriteFailed)
+            },
+            timeout,
+        )
+        .await
+    }
+    .await;
+    step.finish(&ret, |_| Some(bytes.len()));
+    ret
+}
+
+/// `None` if the connection was closed before any byte was received,
+/// only for `ReadUntil::Prompt`, e.g. the CLI timeout expired.
+#[cfg(feature = "_async")]
+pub(crate) async fn read_async(
+    stream: &mut AsyncStream,
+    until: ReadUntil<'_>,
+    options: &ClientOptions,
+    deadline: Deadline,
+) -> Result<Option<Vec<u8>>, ClientSendError> {
+    let step = Step::start("read");
+    let mut response = Vec::with_capacity(BUF_SIZE);
+    let ret = read_with_async(stream, options, deadline, |bytes| {
+        on_read(&mut response, bytes, until, options)
+    })
+    .await;
+    step.finish(&ret, |x| x.as_ref().map(Vec::len));
+    ret
+}
+
+/// Reads the response to `prompt`, which is only the prompt of the session.
+#[cfg(feature = "_async")]
+pub(crate) async fn read_prompt_async(
+    stream: &mut AsyncStream,
+    options: &ClientOptions,
+    deadline: Deadline,
+) -> Result<Prompt, ClientSendError> {
+    let step = Step::start("read");
+    let mut response = vec![];
+    let ret = read_with_async(stream, options, deadline, |bytes| {
+        on_read_prompt(&mut response, bytes, options)
+    })
+    .await;
+    step.finish(&ret, |_| None);
+    ret
+}
+
+/// Reads the responses of `count` pipelined commands, every one is delimited by the prompt.
+#[cfg(feature = "_async")]
+pub(crate) async fn read_prompts_async(
+    stream: &mut AsyncStream,
+    prompt: &Prompt,
+    count: usize,
+    options: &ClientOptions,
+    deadline: Deadline,
+) -> Result<Vec<Vec<u8>>, ClientSendError> {
+    if count == 0 {
+        return Ok(vec![]);
+    }
+
+    let step = Step::start("read");
+    let mut responses = PromptResponses::new(prompt, count);
+    let ret = read_with_async(stream, options, deadline, |bytes| {
+        responses.on_read(bytes, options)
+    })
+    .await;
+    step.finish(&ret, |x| Some(x.iter().map(Vec::len).sum()));
+    ret
+}
+
+// Reads until `on_read` returns a value, it is called with the bytes of every read, empty at EOF.
+#[cfg(feature = "_async")]
+async fn read_with_async<T>(
+    stream: &mut AsyncStream,
+    options: &ClientOptions,
+    deadline: Deadline,
+    mut on_read: impl FnMut(&[u8]) -> Result<Option<T>, ClientSendError>,
+) -> Result<T, ClientSendError> {
+    let mut buf = vec![0; BUF_SIZE];
+    loop {
+        let timeout = deadline.pick(options.read_timeout, TimeoutKind::Read)?;
+        let n = with_timeout(
+            options.runtime,
+            async {
+                runtime::read(stream, &mut buf)
+                    .await
+                    .map_err(ClientSendError::ReadFailed)
+            },
+            timeout,
+        )
+        .await?;
+
+        if let Some(ret) = on_read(&buf[..n])? {
+            return Ok(ret);
+        }
+    }
+}
+
+//
+#[allow(clippy::type_complexity)]
+fn on_read(
+    response: &mut Vec<u8>,
+    bytes: &[u8],
+    until: ReadUntil<'_>,
+    options: &ClientOptions,
+) -> Result<Option<Option<Vec<u8>>>, ClientSendError> {
+    if bytes.is_empty() {
+        return match until {
+            ReadUntil::Eof => Ok(Some(Some(core::mem::take(response)))),
+            ReadUntil::Prompt(_) if response.is_empty() => Ok(Some(None)),
+            ReadUntil::Prompt(_) => Err(unexpected_eof()),
+        };
+    }
+
+    response.extend_from_slice(bytes);
+    check_response_size(response, options)?;
+
+    if let ReadUntil::Prompt(prompt) = until {
+        if let Some(len) = prompt.find_end(response, 0) {
+            response.truncate(len);
+            return Ok(Some(Some(core::mem::take(response))));
+        }
+    }
+
+    Ok(None)
+}
+
+fn on_read_prompt(
+    response: &mut Vec<u8>,
+    bytes: &[u8],
+    options: &ClientOptions,
+) -> Result<Option<Prompt>, ClientSendError> {
+    if bytes.is_empty() {
+        return Err(unexpected_eof());
+    }
+
+    response.extend_from_slice(bytes);
+    check_response_size(response, options)?;
+
+    Ok(Prompt::from_response(response))
+}
+
+//
+// The responses of pipelined commands, the bytes after the last prompt found are pending.
+struct PromptResponses {
+    prompt: Prompt,
+    count: usize,
+    responses: Vec<Vec<u8>>,
+    pending: Vec<u8>,
+    scanned: usize,
+}
+
+impl PromptResponses {
+    fn new(prompt: &Prompt, count: usize) -> Self {
+        Self {
+            prompt: prompt.clone(),
+            count,
+            responses: Vec::with_capacity(count),
+            pending: Vec::with_capacity(BUF_SIZE),
+            scanned: 0,
+        }
+    }
+
+    // The responses, once all of them were read.
+    fn on_read(
+        &mut self,
+        bytes: &[u8],
+        options: &ClientOptions,
+    ) -> Result<Option<Vec<Vec<u8>>>, ClientSendError> {
+        if bytes.is_empty() {
+            return Err(unexpected_eof());
+        }
+
+        self.pending.extend_from_slice(bytes);
+
+        while self.responses.len() < self.count {
+            match self.prompt.split(&self.pending, self.scanned) {
+                Some((len, next)) => {
+                    let rest = self.pending.split_off(next);
+                    let mut response = core::mem::replace(&mut self.pending, rest);
+                    response.truncate(len);
+                    check_response_size(&response, options)?;
+                    self.responses.push(response);
+                    self.scanned = 0;
+                }
+                None => {
+                    // The last line may be an incomplete prompt.
+                    if let Some(i) = self.pending.iter().rposition(|x| *x == b'\n') {
+                        self.scanned = i;
+                    }
+                    check_response_size(&self.pending, options)?;
+                    return Ok(None);
+                }
+            }
+        }
+
+        Ok(Some(core::mem::take(&mut self.responses)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(prompt: &str, count: usize, reads: &[&[u8]]) -> Option<Vec<Vec<u8>>> {
+        let prompt = Prompt::from_response(format!("\n{}> ", prompt).as_bytes()).unwrap();
+        let options = ClientOptions::default();
+        let mut responses = PromptResponses::new(&prompt, count);
+        let mut ret = None;
+        for bytes in reads {
+            assert!(ret.is_none());
+            ret = responses.on_read(bytes, &options).unwrap();
+        }
+        ret
+    }
+
+    #[test]
+    fn test_prompt_responses_with_prompt_split() {
+        assert_eq!(
+            read_all("", 1, &[b"admin\n\n", b"> "]),
+            Some(vec![b"admin\n\n".to_vec()])
+        );
+        assert_eq!(
+            read_all("", 2, &[b"admin\n", b"\n>", b" user\n\n> "]),
+            Some(vec![b"admin\n\n".to_vec(), b"user\n\n".to_vec()])
+        );
+        assert_eq!(read_all("", 1, &[b"admin\n\n", b">"]), None);
+    }
+
+    #[test]
+    fn test_prompt_responses_with_one_read() {
+        assert_eq!(
+            read_all("", 3, &[b"a\n\n> b\nc\n\n> d\n\n> "]),
+            Some(vec![
+                b"a\n\n".to_vec(),
+                b"b\nc\n\n".to_vec(),
+                b"d\n\n".to_vec()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_prompt_responses_with_empty_response() {
+        assert_eq!(
+            read_all("", 3, &[b"\n> a\n\n> ", b"\n> "]),
+            Some(vec![b"\n".to_vec(), b"a\n\n".to_vec(), b"\n".to_vec()])
+        );
+    }
+
+    #[test]
+    fn test_prompt_responses_with_named_prompt() {
+        assert_eq!(
+            read_all(
+                "master",
+                2,
+                &[b"a\n> b\n\n> c\n\nmaster> d\n\nmas", b"ter> "]
+            ),
+            Some(vec![b"a\n> b\n\n> c\n\n".to_vec(), b"d\n\n".to_vec()])
+        );
+        assert_eq!(
+            read_all("1", 2, &[b"a\n\n1> \n1> "]),
+            Some(vec![b"a\n\n".to_vec(), b"\n".to_vec()])
+        );
+    }
+
+    #[test]
+    fn test_prompt_responses_with_eof() {
+        let prompt = Prompt::from_response(b"\n> ").unwrap();
+        let options = ClientOptions::default();
+        let mut responses = PromptResponses::new(&prompt, 2);
+        assert!(responses.on_read(b"a\n\n> ", &options).unwrap().is_none());
+        assert!(responses.on_read(b"", &options).is_err());
+    }
+
+    #[test]
+    fn test_on_read() {
+        let prompt = Prompt::from_response(b"\n> ").unwrap();
+        let options = ClientOptions::default();
+        let mut response = vec![];
+        let until = ReadUntil::Prompt(&prompt);
+        assert_eq!(
+            on_read(&mut response, b"a\n> b\n", until, &options).unwrap(),
+            None
+        );
+        assert_eq!(
+            on_read(&mut response, b"\n> ", until, &options).unwrap(),
+            Some(Some(b"a\n> b\n\n".to_vec()))
+        );
+        assert_eq!(
+            on_read(&mut response, b"", until, &options).unwrap(),
+            Some(None)
+        );
+
+        let mut response = vec![];
+        assert_eq!(
+            on_read(&mut response, b"\nmaster> ", ReadUntil::Eof, &options).unwrap(),
+            None
+        );
+        assert_eq!(
+            on_read_prompt(&mut vec![], b"\nmaster> ", &options).unwrap(),
+            Prompt::from_response(b"\nmaster> ")
+        );
+    }
+}
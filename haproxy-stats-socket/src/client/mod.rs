@@ -1,134 +1,407 @@
 use core::fmt;
+#[cfg(feature = "_async")]
+use core::future::Future;
 use std::{
-    io::{Error as IoError, Read as _, Write as _},
-    net::{SocketAddr, TcpStream},
-    os::unix::net::UnixStream,
-    path::Path,
+    io::Error as IoError, net::SocketAddr, path::Path, slice, sync::Arc, thread, time::Instant,
 };
 
-use futures_util_either::Either;
-use haproxy_stats::Command;
-use tokio::{
-    io::{AsyncReadExt as _, AsyncWriteExt as _},
-    net::{TcpStream as TokioTcpStream, UnixStream as TokioUnixStream},
+use haproxy_stats::{CliLevel, Command, Commands};
+
+use crate::{
+    master::ProcessTarget,
+    trace::SendSpan,
+    transport::{BlockingStream, Transport},
 };
 
 //
+mod builder;
+mod impl_show_cli_level;
 mod impl_show_env;
 mod impl_show_info;
+mod impl_show_proc;
 mod impl_show_stat;
+mod impl_wait;
+pub(crate) mod io;
+pub(crate) mod prompt;
+mod retry;
+#[cfg(feature = "_async")]
+mod runtime;
+mod stream;
 
+pub use builder::ClientBuilder;
+pub use impl_show_cli_level::ClientShowCliLevelError;
 pub use impl_show_env::ClientShowEnvError;
 pub use impl_show_info::ClientShowInfoError;
+pub use impl_show_proc::ClientShowProcError;
 pub use impl_show_stat::ClientShowStatError;
+pub use impl_wait::ClientWaitError;
+pub use retry::RetryPolicy;
+#[cfg(feature = "_async")]
+pub use runtime::AsyncRuntime;
+#[cfg(feature = "_async")]
+pub use stream::AsyncResponseReader;
+pub use stream::ResponseReader;
+
+use builder::ClientOptions;
+use io::{connect, read, read_prompt, read_prompts, unexpected_eof, write, Deadline, ReadUntil};
+#[cfg(feature = "_async")]
+use io::{
+    connect_async, read_async, read_prompt_async, read_prompts_async, write_async, AsyncStream,
+};
+use prompt::Prompt;
 
 //
+#[derive(Debug, Clone)]
 pub struct Client {
-    connect_info: ConnectInfo,
-}
-
-enum ConnectInfo {
-    Tcp(SocketAddr),
-    Unix(Box<Path>),
+    transport: Arc<dyn Transport>,
+    options: ClientOptions,
 }
 
 impl Client {
     pub fn with_tcp(addr: impl Into<SocketAddr>) -> Self {
-        Self {
-            connect_info: ConnectInfo::Tcp(addr.into()),
-        }
+        ClientBuilder::with_tcp(addr).build()
     }
 
     pub fn with_unix(path: impl AsRef<Path>) -> Self {
-        Self {
-            connect_info: ConnectInfo::Unix(path.as_ref().into()),
-        }
+        ClientBuilder::with_unix(path).build()
+    }
+
+    pub fn with_transport(transport: impl Transport) -> Self {
+        ClientBuilder::with_transport(transport).build()
+    }
+
+    pub fn transport(&self) -> &dyn Transport {
+        self.transport.as_ref()
     }
 
     pub fn send(&self, command: &Command) -> Result<Vec<u8>, ClientSendError> {
-        let write_bytes = command.to_write_bytes();
+        self.options.check_level(command)?;
+        SendSpan::new([command], self.transport())
+            .in_scope(|| self.with_retry(command.is_idempotent(), || self.send_once(command)))
+    }
 
-        //
-        let mut stream = match &self.connect_info {
-            ConnectInfo::Tcp(addr) => {
-                Either::Left(TcpStream::connect(addr).map_err(ClientSendError::ConnectFailed)?)
+    #[cfg(feature = "_async")]
+    pub async fn send_async(&self, command: &Command) -> Result<Vec<u8>, ClientSendError> {
+        self.options.check_level(command)?;
+        SendSpan::new([command], self.transport())
+            .instrument(
+                self.with_retry_async(command.is_idempotent(), || self.send_async_once(command)),
+            )
+            .await
+    }
+
+    /// Sends the commands pipelined over one connection in interactive mode,
+    /// every response is delimited by the prompt.
+    pub fn send_multiple(&self, commands: Commands<'_>) -> Result<Vec<Vec<u8>>, ClientSendError> {
+        for command in commands.0 {
+            self.options.check_level(command)?;
+        }
+        SendSpan::new(commands.0, self.transport()).in_scope(|| {
+            self.with_retry(commands.is_idempotent(), || {
+                self.send_multiple_once(&commands)
+            })
+        })
+    }
+
+    /// Sends the commands pipelined over one connection in interactive mode,
+    /// every response is delimited by the prompt.
+    #[cfg(feature = "_async")]
+    pub async fn send_multiple_async(
+        &self,
+        commands: Commands<'_>,
+    ) -> Result<Vec<Vec<u8>>, ClientSendError> {
+        for command in commands.0 {
+            self.options.check_level(command)?;
+        }
+        SendSpan::new(commands.0, self.transport())
+            .instrument(self.with_retry_async(commands.is_idempotent(), || {
+                self.send_multiple_async_once(&commands)
+            }))
+            .await
+    }
+
+    fn with_retry<T>(
+        &self,
+        idempotent: bool,
+        f: impl Fn() -> Result<T, ClientSendError>,
+    ) -> Result<T, ClientSendError> {
+        let mut retry = 0;
+        loop {
+            match f() {
+                Ok(x) => return Ok(x),
+                Err(err) => match self
+                    .options
+                    .retry_policy
+                    .as_ref()
+                    .and_then(|x| x.should_retry(retry, idempotent, &err))
+                {
+                    Some(backoff) => {
+                        thread::sleep(self.options.remaining().map_or(backoff, |x| x.min(backoff)));
+                        retry += 1;
+                    }
+                    None => return Err(err),
+                },
             }
-            ConnectInfo::Unix(path) => {
-                Either::Right(UnixStream::connect(path).map_err(ClientSendError::ConnectFailed)?)
+        }
+    }
+
+    #[cfg(feature = "_async")]
+    async fn with_retry_async<T, Fut>(
+        &self,
+        idempotent: bool,
+        f: impl Fn() -> Fut,
+    ) -> Result<T, ClientSendError>
+    where
+        Fut: Future<Output = Result<T, ClientSendError>>,
+    {
+        let mut retry = 0;
+        loop {
+            match f().await {
+                Ok(x) => return Ok(x),
+                Err(err) => match self
+                    .options
+                    .retry_policy
+                    .as_ref()
+                    .and_then(|x| x.should_retry(retry, idempotent, &err))
+                {
+                    Some(backoff) => {
+                        self.options
+                            .runtime
+                            .sleep(self.options.remaining().map_or(backoff, |x| x.min(backoff)))
+                            .await;
+                        retry += 1;
+                    }
+                    None => return Err(err),
+                },
             }
-        };
+        }
+    }
+
+    fn send_once(&self, command: &Command) -> Result<Vec<u8>, ClientSendError> {
+        // The modes and the level are set in interactive mode, to keep the response apart.
+        if !self.options.setup_commands([command]).is_empty() {
+            let mut responses =
+                self.send_multiple_once(&Commands::new(slice::from_ref(command)))?;
+            return Ok(responses.pop().unwrap_or_default());
+        }
+
+        let deadline = self.options.new_deadline();
 
         //
-        stream
-            .write_all(&write_bytes[..])
-            .map_err(ClientSendError::WriteFailed)?;
+        let mut stream = connect(self.transport.as_ref(), &self.options, deadline)?;
 
         //
-        let mut response: Vec<u8> = Vec::with_capacity(2048);
-        stream
-            .read_to_end(&mut response)
-            .map_err(ClientSendError::ReadFailed)?;
+        write(
+            &mut stream,
+            &self.options.to_write_bytes(command)[..],
+            deadline,
+        )?;
 
-        Ok(response)
+        //
+        let response = read(&mut stream, ReadUntil::Eof, &self.options, deadline)?;
+
+        Ok(response.unwrap_or_default())
     }
 
-    pub async fn send_async(&self, command: &Command) -> Result<Vec<u8>, ClientSendError> {
-        let write_bytes = command.to_write_bytes();
+    #[cfg(feature = "_async")]
+    async fn send_async_once(&self, command: &Command) -> Result<Vec<u8>, ClientSendError> {
+        // The modes and the level are set in interactive mode, to keep the response apart.
+        if !self.options.setup_commands([command]).is_empty() {
+            let mut responses = self
+                .send_multiple_async_once(&Commands::new(slice::from_ref(command)))
+                .await?;
+            return Ok(responses.pop().unwrap_or_default());
+        }
+
+        let deadline = self.options.new_deadline();
 
         //
-        let mut stream = match &self.connect_info {
-            ConnectInfo::Tcp(addr) => Either::Left(
-                TokioTcpStream::connect(addr)
-                    .await
-                    .map_err(ClientSendError::ConnectFailed)?,
-            ),
-            ConnectInfo::Unix(path) => Either::Right(
-                TokioUnixStream::connect(path)
-                    .await
-                    .map_err(ClientSendError::ConnectFailed)?,
-            ),
-        };
+        let mut stream = connect_async(self.transport.as_ref(), &self.options, deadline).await?;
 
         //
-        stream
-            .write_all(&write_bytes[..])
-            .await
-            .map_err(ClientSendError::WriteFailed)?;
+        write_async(
+            &mut stream,
+            &self.options.to_write_bytes(command)[..],
+            &self.options,
+            deadline,
+        )
+        .await?;
 
         //
-        let mut response: Vec<u8> = Vec::with_capacity(2048);
-        let mut buf = vec![0; 2048];
-        loop {
-            let n = stream
-                .read(&mut buf)
-                .await
-                .map_err(ClientSendError::ReadFailed)?;
+        let response = read_async(&mut stream, ReadUntil::Eof, &self.options, deadline).await?;
 
-            if n == 0 {
-                break;
-            }
-            response.extend_from_slice(&buf[..n]);
+        Ok(response.unwrap_or_default())
+    }
+
+    fn send_multiple_once(&self, commands: &Commands<'_>) -> Result<Vec<Vec<u8>>, ClientSendError> {
+        let deadline = self.options.new_deadline();
+
+        //
+        let (mut stream, prompt) = self.connect_prompt(commands.0, deadline)?;
+
+        // Pipelined, the responses are read once all the commands are written.
+        let bytes = commands
+            .0
+            .iter()
+            .flat_map(|x| self.options.to_write_bytes(x))
+            .collect::<Vec<_>>();
+        write(&mut stream, &bytes[..], deadline)?;
+
+        let responses = read_prompts(
+            &mut stream,
+            &prompt,
+            commands.0.len(),
+            &self.options,
+            deadline,
+        )?;
+
+        //
+        write(&mut stream, &Command::quit().to_write_bytes()[..], deadline)?;
+
+        Ok(responses)
+    }
+
+    #[cfg(feature = "_async")]
+    async fn send_multiple_async_once(
+        &self,
+        commands: &Commands<'_>,
+    ) -> Result<Vec<Vec<u8>>, ClientSendError> {
+        let deadline = self.options.new_deadline();
+
+        //
+        let (mut stream, prompt) = self.connect_prompt_async(commands.0, deadline).await?;
+
+        // Pipelined, the responses are read once all the commands are written.
+        let bytes = commands
+            .0
+            .iter()
+            .flat_map(|x| self.options.to_write_bytes(x))
+            .collect::<Vec<_>>();
+        write_async(&mut stream, &bytes[..], &self.options, deadline).await?;
+
+        let responses = read_prompts_async(
+            &mut stream,
+            &prompt,
+            commands.0.len(),
+            &self.options,
+            deadline,
+        )
+        .await?;
+
+        //
+        write_async(
+            &mut stream,
+            &Command::quit().to_write_bytes()[..],
+            &self.options,
+            deadline,
+        )
+        .await?;
+
+        Ok(responses)
+    }
+
+    /// Connects, switches to interactive mode, turns on the modes needed by the commands
+    /// and lowers the level.
+    pub(crate) fn connect_prompt(
+        &self,
+        commands: &[Command],
+        deadline: Deadline,
+    ) -> Result<(BlockingStream, Prompt), ClientSendError> {
+        let mut stream = connect(self.transport.as_ref(), &self.options, deadline)?;
+
+        write(
+            &mut stream,
+            &Command::prompt().to_write_bytes()[..],
+            deadline,
+        )?;
+        let prompt = read_prompt(&mut stream, &self.options, deadline)?;
+
+        for command in self.options.setup_commands(commands) {
+            write(&mut stream, &command.to_write_bytes()[..], deadline)?;
+            read(
+                &mut stream,
+                ReadUntil::Prompt(&prompt),
+                &self.options,
+                deadline,
+            )?
+            .ok_or_else(unexpected_eof)?;
+        }
+
+        Ok((stream, prompt))
+    }
+
+    /// Connects, switches to interactive mode, turns on the modes needed by the commands
+    /// and lowers the level.
+    #[cfg(feature = "_async")]
+    pub(crate) async fn connect_prompt_async(
+        &self,
+        commands: &[Command],
+        deadline: Deadline,
+    ) -> Result<(AsyncStream, Prompt), ClientSendError> {
+        let mut stream = connect_async(self.transport.as_ref(), &self.options, deadline).await?;
+
+        write_async(
+            &mut stream,
+            &Command::prompt().to_write_bytes()[..],
+            &self.options,
+            deadline,
+        )
+        .await?;
+        let prompt = read_prompt_async(&mut stream, &self.options, deadline).await?;
+
+        for command in self.options.setup_commands(commands) {
+            write_async(
+                &mut stream,
+                &command.to_write_bytes()[..],
+                &self.options,
+                deadline,
+            )
+            .await?;
+            read_async(
+                &mut stream,
+                ReadUntil::Prompt(&prompt),
+                &self.options,
+                deadline,
+            )
+            .await?
+            .ok_or_else(unexpected_eof)?;
         }
 
-        Ok(response)
+        Ok((stream, prompt))
     }
 
-    // TODO,
-    // pub fn send_multiple(&self, commands: Commands<'_>) -> Result<Vec<Vec<u8>>, ClientSendError> {
-    //     let _write_bytes = commands.to_write_bytes();
+    pub fn level(&self) -> Option<CliLevel> {
+        self.options.level
+    }
+
+    /// See `ClientBuilder::level`.
+    pub fn with_level(&self, level: CliLevel) -> Self {
+        let mut client = self.clone();
+        client.options.level = Some(level);
+        client
+    }
 
-    //     todo!()
-    // }
+    #[cfg(feature = "_async")]
+    pub(crate) fn options(&self) -> &ClientOptions {
+        &self.options
+    }
 
-    // TODO,
-    // pub async fn send_multiple_async(
-    //     &self,
-    //     commands: Commands<'_>,
-    // ) -> Result<Vec<Vec<u8>>, ClientSendError> {
-    //     let _write_bytes = commands.to_write_bytes();
+    pub(crate) fn with_target(&self, target: ProcessTarget) -> Self {
+        let mut client = self.clone();
+        client.options.target = Some(target);
+        client
+    }
 
-    //     todo!()
-    // }
+    /// Keeps the shorter one of the timeouts.
+    /// Every send and retry fails with `ClientSendError::Timeout` after `at`.
+    pub(crate) fn with_deadline(&self, at: Instant) -> Self {
+        let mut client = self.clone();
+        client.options.deadline = Some(match client.options.deadline {
+            Some(x) => x.min(at),
+            None => at,
+        });
+        client
+    }
 }
 
 //
@@ -137,8 +410,32 @@ impl Client {
 #[derive(Debug)]
 pub enum ClientSendError {
     ConnectFailed(IoError),
+    ConnectTimeout,
     WriteFailed(IoError),
     ReadFailed(IoError),
+    ReadTimeout,
+    Timeout,
+    ResponseTooLarge,
+    LevelInsufficient {
+        command: Box<str>,
+        required: CliLevel,
+        level: CliLevel,
+    },
+}
+
+impl ClientSendError {
+    pub fn kind(&self) -> ClientSendErrorKind {
+        match self {
+            Self::ConnectFailed(_) => ClientSendErrorKind::ConnectFailed,
+            Self::ConnectTimeout => ClientSendErrorKind::ConnectTimeout,
+            Self::WriteFailed(_) => ClientSendErrorKind::WriteFailed,
+            Self::ReadFailed(_) => ClientSendErrorKind::ReadFailed,
+            Self::ReadTimeout => ClientSendErrorKind::ReadTimeout,
+            Self::Timeout => ClientSendErrorKind::Timeout,
+            Self::ResponseTooLarge => ClientSendErrorKind::ResponseTooLarge,
+            Self::LevelInsufficient { .. } => ClientSendErrorKind::LevelInsufficient,
+        }
+    }
 }
 
 impl fmt::Display for ClientSendError {
@@ -148,3 +445,16 @@ impl fmt::Display for ClientSendError {
 }
 
 impl std::error::Error for ClientSendError {}
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClientSendErrorKind {
+    ConnectFailed,
+    ConnectTimeout,
+    WriteFailed,
+    ReadFailed,
+    ReadTimeout,
+    Timeout,
+    ResponseTooLarge,
+    LevelInsufficient,
+}
@@ -0,0 +1,156 @@
+//
+// In interactive mode, every response is followed by "\n> ", or "\nmaster> " on the master CLI.
+// The response is empty or ends with a new line, so a line starting with "> " inside it,
+// right after a non-empty line, is not the prompt.
+const PROMPT_SUFFIX: &[u8] = b"> ";
+
+//
+/// The prompt of a session, learned from the response to `prompt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Prompt {
+    // `None` until learned, any name is accepted.
+    name: Option<Box<[u8]>>,
+}
+
+impl Prompt {
+    pub(crate) fn any() -> Self {
+        Self { name: None }
+    }
+
+    /// From the response to `prompt`, which is only the prompt, e.g. `\nmaster> `.
+    pub(crate) fn from_response(buf: &[u8]) -> Option<Self> {
+        let len = Self::any().find_end(buf, 0)?;
+        let name = &buf[len..buf.len() - PROMPT_SUFFIX.len()];
+        Some(Self {
+            name: Some(name.into()),
+        })
+    }
+
+    /// Returns the length of the response without the prompt, if `buf` ends with it.
+    ///
+    /// The prompt is not searched in `buf[..from]`, e.g. the last byte already given to the caller.
+    pub(crate) fn find_end(&self, buf: &[u8], from: usize) -> Option<usize> {
+        if !buf.ends_with(PROMPT_SUFFIX) {
+            return None;
+        }
+
+        let nl = buf.iter().rposition(|x| *x == b'\n')?;
+        if nl < from {
+            return None;
+        }
+        match self.end_at(buf, nl) {
+            Some(end) if end == buf.len() => Some(nl + 1),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of the first response and where the next one starts,
+    /// the responses of pipelined commands may be read at once.
+    ///
+    /// Lines before `from` are not searched again.
+    pub(crate) fn split(&self, buf: &[u8], from: usize) -> Option<(usize, usize)> {
+        let mut pos = from;
+        while let Some(i) = buf[pos..].iter().position(|x| *x == b'\n') {
+            let nl = pos + i;
+            if let Some(end) = self.end_at(buf, nl) {
+                return Some((nl + 1, end));
+            }
+            pos = nl + 1;
+        }
+        None
+    }
+
+    // Where the prompt starting with the new line at `nl` ends, if it is one.
+    fn end_at(&self, buf: &[u8], nl: usize) -> Option<usize> {
+        if nl > 0 && buf[nl - 1] != b'\n' {
+            return None;
+        }
+
+        let line = &buf[nl + 1..];
+        let name_len = match &self.name {
+            Some(name) if line.starts_with(name) => name.len(),
+            Some(_) => return None,
+            None => line.iter().take_while(|x| is_prompt_name_char(x)).count(),
+        };
+        if line[name_len..].starts_with(PROMPT_SUFFIX) {
+            Some(nl + 1 + name_len + PROMPT_SUFFIX.len())
+        } else {
+            None
+        }
+    }
+}
+
+fn is_prompt_name_char(x: &u8) -> bool {
+    x.is_ascii_alphanumeric() || matches!(x, b'_' | b'-' | b'@' | b'!')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt(name: &str) -> Prompt {
+        Prompt::from_response(format!("\n{}> ", name).as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_prompt_from_response() {
+        assert_eq!(
+            prompt(""),
+            Prompt {
+                name: Some(b""[..].into())
+            }
+        );
+        assert_eq!(
+            prompt("master"),
+            Prompt {
+                name: Some(b"master"[..].into())
+            }
+        );
+        assert_eq!(
+            prompt("1"),
+            Prompt {
+                name: Some(b"1"[..].into())
+            }
+        );
+        assert_eq!(Prompt::from_response(b"Unknown command.\n"), None);
+    }
+
+    #[test]
+    fn test_prompt_find_end() {
+        let prompt = prompt("");
+        assert_eq!(prompt.find_end(b"admin\n\n> ", 0), Some(7));
+        // Empty response.
+        assert_eq!(prompt.find_end(b"\n> ", 0), Some(1));
+        assert_eq!(prompt.find_end(b"admin\n\n", 0), None);
+        assert_eq!(prompt.find_end(b"admin\n\n>", 0), None);
+        // Inside the response.
+        assert_eq!(prompt.find_end(b"admin\n> ", 0), None);
+        // After the last byte given.
+        assert_eq!(prompt.find_end(b"a\n\n> ", 1), Some(3));
+        assert_eq!(prompt.find_end(b"a\n> ", 1), None);
+        assert_eq!(prompt.find_end(b"\n> ", 1), None);
+
+        assert_eq!(
+            self::prompt("master").find_end(b"x\n\nmaster> ", 0),
+            Some(3)
+        );
+        assert_eq!(self::prompt("master").find_end(b"x\n\n> ", 0), None);
+        assert_eq!(self::prompt("1").find_end(b"x\n\n1> ", 0), Some(3));
+        assert_eq!(self::prompt("1").find_end(b"x\n\nmaster> ", 0), None);
+    }
+
+    #[test]
+    fn test_prompt_split() {
+        let prompt = prompt("");
+        assert_eq!(prompt.split(b"a\n\n> b\n\n> ", 0), Some((3, 5)));
+        assert_eq!(prompt.split(b"\n> b\n\n> ", 0), Some((1, 3)));
+        assert_eq!(prompt.split(b"a\n\n", 0), None);
+
+        // A "> " line inside a payload.
+        let buf = b"Name: x\n> quoted\nfoo\n\n> ";
+        assert_eq!(prompt.split(buf, 0), Some((buf.len() - 2, buf.len())));
+
+        let prompt = self::prompt("master");
+        assert_eq!(prompt.split(b"a\n\n> b\n\nmaster> c", 0), Some((8, 16)));
+    }
+}
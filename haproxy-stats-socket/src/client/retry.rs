@@ -0,0 +1,162 @@
+use core::{
+    hash::{BuildHasher as _, Hasher as _},
+    time::Duration,
+};
+use std::{collections::hash_map::RandomState, time::SystemTime};
+
+use super::{ClientSendError, ClientSendErrorKind};
+
+//
+/// Opt-in retry policy for transient socket failures, e.g. during master-worker reloads.
+///
+/// Mutating commands are never retried, unless marked by `Command::mark_idempotent`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_retries: usize,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retryable: Vec<ClientSendErrorKind>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+            jitter: true,
+            retryable: vec![
+                ClientSendErrorKind::ConnectFailed,
+                ClientSendErrorKind::ConnectTimeout,
+                ClientSendErrorKind::WriteFailed,
+                ClientSendErrorKind::ReadFailed,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: usize) -> Self {
+        Self {
+            max_retries,
+            ..Default::default()
+        }
+    }
+
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn retryable(mut self, kinds: impl Into<Vec<ClientSendErrorKind>>) -> Self {
+        self.retryable = kinds.into();
+        self
+    }
+
+    pub fn max_retries(&self) -> usize {
+        self.max_retries
+    }
+
+    pub fn is_retryable(&self, err: &ClientSendError) -> bool {
+        self.retryable.contains(&err.kind())
+    }
+
+    /// Exponential backoff, with jitter it is random between the half and the full.
+    pub fn backoff(&self, retry: usize) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1_u32.checked_shl(retry as u32).unwrap_or(u32::MAX))
+            .min(self.max_backoff);
+
+        if self.jitter {
+            let half = backoff / 2;
+            half + half.mul_f64(random_f64())
+        } else {
+            backoff
+        }
+    }
+
+    pub(crate) fn should_retry(
+        &self,
+        retry: usize,
+        idempotent: bool,
+        err: &ClientSendError,
+    ) -> Option<Duration> {
+        if idempotent && retry < self.max_retries && self.is_retryable(err) {
+            Some(self.backoff(retry))
+        } else {
+            None
+        }
+    }
+}
+
+// [0, 1)
+fn random_f64() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(x) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        hasher.write_u128(x.as_nanos());
+    }
+    (hasher.finish() >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new(10)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(1))
+            .jitter(false);
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(3), Duration::from_millis(800));
+        assert_eq!(policy.backoff(4), Duration::from_secs(1));
+        assert_eq!(policy.backoff(64), Duration::from_secs(1));
+        assert_eq!(policy.backoff(usize::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_backoff_with_jitter() {
+        let policy = RetryPolicy::new(10)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(1));
+        for retry in 0..8 {
+            let max = Duration::from_millis(100 << retry).min(Duration::from_secs(1));
+            for _ in 0..100 {
+                let backoff = policy.backoff(retry);
+                assert!(backoff >= max / 2, "{:?}", backoff);
+                assert!(backoff <= max, "{:?}", backoff);
+            }
+        }
+    }
+
+    #[test]
+    fn test_should_retry() {
+        let policy = RetryPolicy::new(2).jitter(false);
+        let err = ClientSendError::ConnectTimeout;
+        assert_eq!(
+            policy.should_retry(0, true, &err),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(policy.should_retry(0, false, &err), None);
+        assert_eq!(policy.should_retry(2, true, &err), None);
+        assert_eq!(
+            policy.should_retry(0, true, &ClientSendError::Timeout),
+            None
+        );
+    }
+}
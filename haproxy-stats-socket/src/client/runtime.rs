@@ -0,0 +1,126 @@
+use core::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::Poll,
+    time::Duration,
+};
+use std::{io::Error as IoError, net::SocketAddr, path::Path};
+
+use futures_io::{AsyncRead as _, AsyncWrite as _};
+
+use crate::transport::AsyncStream;
+#[cfg(feature = "tokio")]
+use crate::transport::TokioIo;
+
+//
+/// Async runtime driving the connections and the timers.
+///
+/// Defaults to tokio when the `tokio` feature is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AsyncRuntime {
+    #[cfg(feature = "tokio")]
+    Tokio,
+    /// async-std, smol, or any executor, with the `async-io` feature.
+    #[cfg(feature = "async-io")]
+    AsyncIo,
+}
+
+impl Default for AsyncRuntime {
+    fn default() -> Self {
+        #[cfg(feature = "tokio")]
+        return Self::Tokio;
+        #[cfg(not(feature = "tokio"))]
+        return Self::AsyncIo;
+    }
+}
+
+impl AsyncRuntime {
+    pub(crate) async fn connect_tcp(self, addr: SocketAddr) -> Result<AsyncStream, IoError> {
+        match self {
+            #[cfg(feature = "tokio")]
+            Self::Tokio => Ok(Box::new(TokioIo(
+                tokio::net::TcpStream::connect(addr).await?,
+            ))),
+            #[cfg(feature = "async-io")]
+            Self::AsyncIo => Ok(Box::new(
+                async_io::Async::<std::net::TcpStream>::connect(addr).await?,
+            )),
+        }
+    }
+
+    pub(crate) async fn connect_unix(self, path: &Path) -> Result<AsyncStream, IoError> {
+        match self {
+            #[cfg(feature = "tokio")]
+            Self::Tokio => Ok(Box::new(TokioIo(
+                tokio::net::UnixStream::connect(path).await?,
+            ))),
+            #[cfg(feature = "async-io")]
+            Self::AsyncIo => Ok(Box::new(
+                async_io::Async::<std::os::unix::net::UnixStream>::connect(path).await?,
+            )),
+        }
+    }
+
+    /// A unix stream connected by std, e.g. to an abstract address.
+    pub(crate) fn unix_from_std(
+        self,
+        stream: std::os::unix::net::UnixStream,
+    ) -> Result<AsyncStream, IoError> {
+        match self {
+            #[cfg(feature = "tokio")]
+            Self::Tokio => {
+                stream.set_nonblocking(true)?;
+                Ok(Box::new(TokioIo(tokio::net::UnixStream::from_std(stream)?)))
+            }
+            #[cfg(feature = "async-io")]
+            Self::AsyncIo => Ok(Box::new(async_io::Async::new(stream)?)),
+        }
+    }
+
+    pub(crate) fn sleep(self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        match self {
+            #[cfg(feature = "tokio")]
+            Self::Tokio => Box::pin(tokio::time::sleep(duration)),
+            #[cfg(feature = "async-io")]
+            Self::AsyncIo => Box::pin(async move {
+                async_io::Timer::after(duration).await;
+            }),
+        }
+    }
+
+    /// `None` if the duration elapsed first.
+    pub(crate) async fn timeout<T>(
+        self,
+        duration: Duration,
+        future: impl Future<Output = T>,
+    ) -> Option<T> {
+        let mut future = Box::pin(future);
+        let mut sleep = self.sleep(duration);
+        poll_fn(|cx| {
+            if let Poll::Ready(x) = future.as_mut().poll(cx) {
+                return Poll::Ready(Some(x));
+            }
+            sleep.as_mut().poll(cx).map(|_| None)
+        })
+        .await
+    }
+}
+
+//
+pub(crate) async fn read(stream: &mut AsyncStream, buf: &mut [u8]) -> Result<usize, IoError> {
+    poll_fn(|cx| Pin::new(&mut *stream).poll_read(cx, buf)).await
+}
+
+pub(crate) async fn write_all(stream: &mut AsyncStream, mut bytes: &[u8]) -> Result<(), IoError> {
+    while !bytes.is_empty() {
+        let n = poll_fn(|cx| Pin::new(&mut *stream).poll_write(cx, bytes)).await?;
+        if n == 0 {
+            return Err(IoError::new(
+                std::io::ErrorKind::WriteZero,
+                "failed to write whole buffer",
+            ));
+        }
+        bytes = &bytes[n..];
+    }
+    poll_fn(|cx| Pin::new(&mut *stream).poll_flush(cx)).await
+}
@@ -0,0 +1,404 @@
+#[cfg(feature = "_async")]
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use std::{
+    io::{Error as IoError, ErrorKind as IoErrorKind, Read},
+    slice,
+};
+
+#[cfg(feature = "_async")]
+use futures_io::AsyncRead;
+use haproxy_stats::Command;
+
+use super::{
+    io::{connect, write},
+    prompt::Prompt,
+    Client, ClientSendError,
+};
+#[cfg(feature = "_async")]
+use super::{
+    io::{connect_async, write_async, AsyncStream},
+    AsyncRuntime,
+};
+use crate::{trace::SendSpan, transport::BlockingStream};
+
+//
+const BUF_SIZE: usize = 2048;
+// Longer last lines are not a prompt, e.g. `\nmaster> `.
+const PROMPT_LINE_MAX_LEN: usize = 64;
+
+//
+impl Client {
+    /// The response is read by the caller, without buffering it nor applying `max_response_size`.
+    ///
+    /// The timeouts apply until the command is written, then only `read_timeout` applies,
+    /// a read fails with `WouldBlock` or `TimedOut`.
+    pub fn send_streaming(&self, command: &Command) -> Result<ResponseReader, ClientSendError> {
+        self.options.check_level(command)?;
+        SendSpan::new([command], self.transport()).in_scope(|| {
+            self.with_retry(command.is_idempotent(), || {
+                self.send_streaming_once(command)
+            })
+        })
+    }
+
+    /// The response is read by the caller, without buffering it nor applying `max_response_size`.
+    ///
+    /// The timeouts apply until the command is written, then only `read_timeout` applies,
+    /// a read fails with `TimedOut` and `ClientSendError::ReadTimeout` inside.
+    #[cfg(feature = "_async")]
+    pub async fn send_streaming_async(
+        &self,
+        command: &Command,
+    ) -> Result<AsyncResponseReader, ClientSendError> {
+        self.options.check_level(command)?;
+        SendSpan::new([command], self.transport())
+            .instrument(self.with_retry_async(command.is_idempotent(), || {
+                self.send_streaming_async_once(command)
+            }))
+            .await
+    }
+
+    fn send_streaming_once(&self, command: &Command) -> Result<ResponseReader, ClientSendError> {
+        let deadline = self.options.new_deadline();
+
+        // The modes and the level are set in interactive mode, the response ends with the prompt.
+        let (mut stream, prompt) = if self.options.setup_commands([command]).is_empty() {
+            (
+                connect(self.transport.as_ref(), &self.options, deadline)?,
+                None,
+            )
+        } else {
+            let (stream, prompt) = self.connect_prompt(slice::from_ref(command), deadline)?;
+            (stream, Some(prompt))
+        };
+
+        //
+        write(
+            &mut stream,
+            &self.options.to_write_bytes(command)[..],
+            deadline,
+        )?;
+
+        stream
+            .set_read_timeout(self.options.read_timeout)
+            .map_err(ClientSendError::ReadFailed)?;
+
+        Ok(ResponseReader {
+            stream,
+            buf: ResponseBuf::new(prompt),
+        })
+    }
+
+    #[cfg(feature = "_async")]
+    async fn send_streaming_async_once(
+        &self,
+        command: &Command,
+    ) -> Result<AsyncResponseReader, ClientSendError> {
+        let deadline = self.options.new_deadline();
+
+        //
+        let (mut stream, prompt) = if self.options.setup_commands([command]).is_empty() {
+            (
+                connect_async(self.transport.as_ref(), &self.options, deadline).await?,
+                None,
+            )
+        } else {
+            let (stream, prompt) = self
+                .connect_prompt_async(slice::from_ref(command), deadline)
+                .await?;
+            (stream, Some(prompt))
+        };
+
+        //
+        write_async(
+            &mut stream,
+            &self.options.to_write_bytes(command)[..],
+            &self.options,
+            deadline,
+        )
+        .await?;
+
+        Ok(AsyncResponseReader {
+            stream,
+            buf: ResponseBuf::new(prompt),
+            runtime: self.options.runtime,
+            read_timeout: self.options.read_timeout,
+            sleep: None,
+        })
+    }
+}
+
+//
+/// The response of `Client::send_streaming`, until EOF or the prompt.
+#[derive(Debug)]
+pub struct ResponseReader {
+    stream: BlockingStream,
+    buf: ResponseBuf,
+}
+
+impl Read for ResponseReader {
+    fn read(&mut self, out: &mut [u8]) -> Result<usize, IoError> {
+        loop {
+            if let Some(n) = self.buf.take(out) {
+                return Ok(n);
+            }
+
+            let mut chunk = [0; BUF_SIZE];
+            let n = self.stream.read(&mut chunk)?;
+            self.buf.on_read(&chunk[..n])?;
+        }
+    }
+}
+
+/// The response of `Client::send_streaming_async`, until EOF or the prompt.
+#[cfg(feature = "_async")]
+pub struct AsyncResponseReader {
+    stream: AsyncStream,
+    buf: ResponseBuf,
+    runtime: AsyncRuntime,
+    read_timeout: Option<Duration>,
+    // Started when a read is pending, until bytes are received.
+    sleep: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+}
+
+#[cfg(feature = "_async")]
+impl fmt::Debug for AsyncResponseReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncResponseReader")
+            .field("stream", &self.stream)
+            .field("buf", &self.buf)
+            .field("runtime", &self.runtime)
+            .field("read_timeout", &self.read_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "_async")]
+impl AsyncRead for AsyncResponseReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        out: &mut [u8],
+    ) -> Poll<Result<usize, IoError>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(n) = this.buf.take(out) {
+                return Poll::Ready(Ok(n));
+            }
+
+            let mut chunk = [0; BUF_SIZE];
+            let n = match Pin::new(&mut this.stream).poll_read(cx, &mut chunk) {
+                Poll::Ready(Ok(n)) => n,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => {
+                    if let Some(timeout) = this.read_timeout {
+                        let runtime = this.runtime;
+                        let sleep = this.sleep.get_or_insert_with(|| runtime.sleep(timeout));
+                        if sleep.as_mut().poll(cx).is_ready() {
+                            this.sleep = None;
+                            return Poll::Ready(Err(IoError::new(
+                                IoErrorKind::TimedOut,
+                                ClientSendError::ReadTimeout,
+                            )));
+                        }
+                    }
+                    return Poll::Pending;
+                }
+            };
+            this.sleep = None;
+            this.buf.on_read(&chunk[..n])?;
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl tokio::io::AsyncRead for AsyncResponseReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<Result<(), IoError>> {
+        let n = match AsyncRead::poll_read(self, cx, buf.initialize_unfilled()) {
+            Poll::Ready(Ok(n)) => n,
+            Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+            Poll::Pending => return Poll::Pending,
+        };
+        buf.advance(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+//
+// Bytes read but not given to the caller yet. In interactive mode,
+// a short last line is kept until it is known not to be the prompt,
+// and so is the last byte given, the prompt follows an empty line.
+#[derive(Debug)]
+struct ResponseBuf {
+    // `None` if the response ends at EOF.
+    prompt: Option<Prompt>,
+    bytes: Vec<u8>,
+    pos: usize,
+    ready: usize,
+    eof: bool,
+}
+
+impl ResponseBuf {
+    fn new(prompt: Option<Prompt>) -> Self {
+        Self {
+            prompt,
+            bytes: Vec::with_capacity(BUF_SIZE),
+            pos: 0,
+            ready: 0,
+            eof: false,
+        }
+    }
+
+    // `None` if more bytes have to be read.
+    fn take(&mut self, out: &mut [u8]) -> Option<usize> {
+        if self.pos < self.ready {
+            let n = out.len().min(self.ready - self.pos);
+            out[..n].copy_from_slice(&self.bytes[self.pos..self.pos + n]);
+            self.pos += n;
+            return Some(n);
+        }
+        if self.eof || out.is_empty() {
+            return Some(0);
+        }
+
+        let n = self.pos.saturating_sub(1);
+        self.bytes.drain(..n);
+        self.ready -= n;
+        self.pos -= n;
+        None
+    }
+
+    fn on_read(&mut self, bytes: &[u8]) -> Result<(), IoError> {
+        if bytes.is_empty() {
+            if self.prompt.is_some() {
+                return Err(IoError::new(
+                    IoErrorKind::UnexpectedEof,
+                    "connection closed before prompt",
+                ));
+            }
+            self.ready = self.bytes.len();
+            self.eof = true;
+            return Ok(());
+        }
+
+        self.bytes.extend_from_slice(bytes);
+
+        let prompt = match &self.prompt {
+            Some(prompt) => prompt,
+            None => {
+                self.ready = self.bytes.len();
+                return Ok(());
+            }
+        };
+        self.ready = match prompt.find_end(&self.bytes, self.ready) {
+            Some(len) => {
+                self.bytes.truncate(len);
+                self.eof = true;
+                len
+            }
+            None => match self.bytes.iter().rposition(|x| *x == b'\n') {
+                Some(i) if i >= self.ready && self.bytes.len() - i <= PROMPT_LINE_MAX_LEN => i,
+                _ if self.bytes.len() - self.ready <= PROMPT_LINE_MAX_LEN => self.ready,
+                _ => self.bytes.len(),
+            },
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reads with a small buffer until the end of the response.
+    fn read_all(buf: &mut ResponseBuf, chunks: &[&[u8]]) -> Vec<u8> {
+        let mut chunks = chunks.iter();
+        let mut out = vec![];
+        let mut tmp = [0; 7];
+        loop {
+            match buf.take(&mut tmp) {
+                Some(0) => return out,
+                Some(n) => out.extend_from_slice(&tmp[..n]),
+                None => buf
+                    .on_read(chunks.next().copied().unwrap_or_default())
+                    .unwrap(),
+            }
+        }
+    }
+
+    fn prompt_buf(name: &str) -> ResponseBuf {
+        let prompt = Prompt::from_response(format!("\n{}> ", name).as_bytes()).unwrap();
+        ResponseBuf::new(Some(prompt))
+    }
+
+    #[test]
+    fn test_response_buf_with_prompt_split() {
+        for name in ["", "master", "1"] {
+            let response = b"Name: HAProxy\n> quoted\n\n".to_vec();
+            let bytes = [&response[..], format!("{}> ", name).as_bytes()].concat();
+
+            for i in 1..bytes.len() {
+                let chunks = [&bytes[..i], &bytes[i..]];
+                assert_eq!(read_all(&mut prompt_buf(name), &chunks), response);
+            }
+
+            let chunks = bytes.chunks(1).collect::<Vec<_>>();
+            assert_eq!(read_all(&mut prompt_buf(name), &chunks), response);
+        }
+    }
+
+    #[test]
+    fn test_response_buf_with_long_last_line() {
+        let line = "x".repeat(PROMPT_LINE_MAX_LEN * 2);
+        let response = format!("Name: HAProxy\n{}\n\n", line).into_bytes();
+        let bytes = [&response[..], b"> "].concat();
+
+        // Not held back, the line is too long to be the prompt.
+        let mut buf = prompt_buf("");
+        buf.on_read(&bytes[..response.len() - 2]).unwrap();
+        let mut out = vec![0; bytes.len()];
+        assert_eq!(buf.take(&mut out), Some(response.len() - 2));
+
+        for i in 1..bytes.len() {
+            let chunks = [&bytes[..i], &bytes[i..]];
+            assert_eq!(read_all(&mut prompt_buf(""), &chunks), response);
+        }
+    }
+
+    #[test]
+    fn test_response_buf_with_short_response() {
+        let bytes = b"ok\n\n> ";
+        for i in 1..bytes.len() {
+            let chunks = [&bytes[..i], &bytes[i..]];
+            assert_eq!(read_all(&mut prompt_buf(""), &chunks), b"ok\n\n");
+        }
+        assert_eq!(read_all(&mut prompt_buf(""), &[bytes]), b"ok\n\n");
+
+        // Empty response.
+        assert_eq!(read_all(&mut prompt_buf(""), &[b"\n", b"> "]), b"\n");
+
+        // Held back until the end.
+        let mut buf = prompt_buf("");
+        buf.on_read(b"ok\n").unwrap();
+        assert_eq!(buf.take(&mut [0; 8]), Some(2));
+        assert_eq!(buf.take(&mut [0; 8]), None);
+        assert!(buf.on_read(b"").is_err());
+    }
+
+    #[test]
+    fn test_response_buf_until_eof() {
+        let mut buf = ResponseBuf::new(None);
+        assert_eq!(read_all(&mut buf, &[b"ok\n", b"\n> ", b""]), b"ok\n\n> ");
+    }
+}
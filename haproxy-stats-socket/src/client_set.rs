@@ -0,0 +1,248 @@
+use core::{fmt, time::Duration};
+#[cfg(feature = "_async")]
+use core::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::Poll,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Instant,
+};
+
+use haproxy_stats::{Command, EnvironmentVariables, Info, Statistic};
+
+use crate::client::{
+    Client, ClientSendError, ClientShowEnvError, ClientShowInfoError, ClientShowStatError,
+};
+
+//
+const DEFAULT_CONCURRENCY: usize = 8;
+
+//
+/// Runs the same command against many nodes, every node has its own result.
+#[derive(Debug, Clone)]
+pub struct ClientSet {
+    nodes: BTreeMap<Box<str>, Client>,
+    concurrency: usize,
+    timeout: Option<Duration>,
+}
+
+pub type ClientSetResults<T, E> = BTreeMap<Box<str>, Result<T, ClientSetNodeError<E>>>;
+
+impl Default for ClientSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientSet {
+    pub fn new() -> Self {
+        Self {
+            nodes: BTreeMap::new(),
+            concurrency: DEFAULT_CONCURRENCY,
+            timeout: None,
+        }
+    }
+
+    /// Replaces the node with the same label.
+    pub fn node(mut self, label: impl AsRef<str>, client: Client) -> Self {
+        self.nodes.insert(label.as_ref().into(), client);
+        self
+    }
+
+    /// Nodes handled at the same time.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// For every node, a slow node fails with `ClientSetNodeError::Timeout`.
+    ///
+    /// It covers all the sends and retries of the node, also in the blocking methods.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (&str, &Client)> {
+        self.nodes
+            .iter()
+            .map(|(label, client)| (label.as_ref(), client))
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Calls `f` for every node, e.g. with `client.admin()` for the mutating commands.
+    #[cfg(feature = "_async")]
+    pub async fn run<T, E, F, Fut>(&self, f: F) -> ClientSetResults<T, E>
+    where
+        F: Fn(Client) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let futures = self.nodes.values().map(|client| {
+            let future = f(client.clone());
+            let runtime = client.options().runtime;
+            let timeout = self.timeout;
+            async move {
+                match timeout {
+                    Some(timeout) => match runtime.timeout(timeout, future).await {
+                        Some(ret) => ret.map_err(ClientSetNodeError::Failed),
+                        None => Err(ClientSetNodeError::Timeout),
+                    },
+                    None => future.await.map_err(ClientSetNodeError::Failed),
+                }
+            }
+        });
+
+        self.nodes
+            .keys()
+            .cloned()
+            .zip(join_limited(futures, self.concurrency).await)
+            .collect()
+    }
+
+    /// Calls `f` for every node, on up to `concurrency` threads.
+    pub fn run_blocking<T, E, F>(&self, f: F) -> ClientSetResults<T, E>
+    where
+        F: Fn(Client) -> Result<T, E> + Sync,
+        T: Send,
+        E: Send,
+    {
+        let nodes = self.nodes.iter().collect::<Vec<_>>();
+        let next = AtomicUsize::new(0);
+        let results = Mutex::new(BTreeMap::new());
+
+        thread::scope(|scope| {
+            for _ in 0..self.concurrency.min(nodes.len()) {
+                scope.spawn(|| {
+                    while let Some(&(label, client)) =
+                        nodes.get(next.fetch_add(1, Ordering::Relaxed))
+                    {
+                        let deadline = self.timeout.map(|x| Instant::now() + x);
+                        let client = match deadline {
+                            Some(at) => client.with_deadline(at),
+                            None => client.clone(),
+                        };
+                        let ret = f(client).map_err(|err| match deadline {
+                            Some(at) if Instant::now() >= at => ClientSetNodeError::Timeout,
+                            _ => ClientSetNodeError::Failed(err),
+                        });
+                        results.lock().expect("").insert(label.clone(), ret);
+                    }
+                });
+            }
+        });
+
+        results.into_inner().expect("")
+    }
+
+    //
+    #[cfg(feature = "_async")]
+    pub async fn send(&self, command: &Command) -> ClientSetResults<Vec<u8>, ClientSendError> {
+        self.run(|client| async move { client.send_async(command).await })
+            .await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn show_info(&self) -> ClientSetResults<Info, ClientShowInfoError> {
+        self.run(|client| async move { client.show_info().await })
+            .await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn show_stat(&self) -> ClientSetResults<Vec<Statistic>, ClientShowStatError> {
+        self.run(|client| async move { client.show_stat().await })
+            .await
+    }
+
+    #[cfg(feature = "_async")]
+    pub async fn show_env(&self) -> ClientSetResults<EnvironmentVariables, ClientShowEnvError> {
+        self.run(|client| async move { client.show_env().await })
+            .await
+    }
+
+    pub fn send_blocking(&self, command: &Command) -> ClientSetResults<Vec<u8>, ClientSendError> {
+        self.run_blocking(|client| client.send(command))
+    }
+
+    pub fn show_info_blocking(&self) -> ClientSetResults<Info, ClientShowInfoError> {
+        self.run_blocking(|client| client.show_info_blocking())
+    }
+
+    pub fn show_stat_blocking(&self) -> ClientSetResults<Vec<Statistic>, ClientShowStatError> {
+        self.run_blocking(|client| client.show_stat_blocking())
+    }
+
+    pub fn show_env_blocking(&self) -> ClientSetResults<EnvironmentVariables, ClientShowEnvError> {
+        self.run_blocking(|client| client.show_env_blocking())
+    }
+}
+
+// Polls up to `limit` futures at the same time, the outputs keep the order.
+#[cfg(feature = "_async")]
+async fn join_limited<Fut: Future>(
+    futures: impl Iterator<Item = Fut>,
+    limit: usize,
+) -> Vec<Fut::Output> {
+    let mut pending = futures.enumerate();
+    let mut running: Vec<(usize, Pin<Box<Fut>>)> = vec![];
+    let mut outputs: Vec<Option<Fut::Output>> = vec![];
+
+    poll_fn(|cx| loop {
+        while running.len() < limit {
+            match pending.next() {
+                Some((i, future)) => {
+                    outputs.push(None);
+                    running.push((i, Box::pin(future)));
+                }
+                None => break,
+            }
+        }
+        if running.is_empty() {
+            return Poll::Ready(());
+        }
+
+        let mut progressed = false;
+        running.retain_mut(|(i, future)| match future.as_mut().poll(cx) {
+            Poll::Ready(output) => {
+                outputs[*i] = Some(output);
+                progressed = true;
+                false
+            }
+            Poll::Pending => true,
+        });
+        if !progressed {
+            return Poll::Pending;
+        }
+    })
+    .await;
+
+    outputs.into_iter().flatten().collect()
+}
+
+//
+#[derive(Debug)]
+pub enum ClientSetNodeError<E> {
+    Failed(E),
+    Timeout,
+}
+
+impl<E: fmt::Debug> fmt::Display for ClientSetNodeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for ClientSetNodeError<E> {}
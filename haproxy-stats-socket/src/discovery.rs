@@ -0,0 +1,549 @@
+use core::fmt;
+use std::{env, fs, io::Error as IoError, path::Path};
+
+use haproxy_stats::{level::CliLevelParseError, CliLevel};
+
+use crate::{
+    address::{HaproxyAddress, HaproxyAddressParseError},
+    client::{Client, ClientBuilder},
+};
+
+//
+const SECTION_KEYWORDS: &[&str] = &[
+    "global",
+    "defaults",
+    "frontend",
+    "backend",
+    "listen",
+    "peers",
+    "resolvers",
+    "userlist",
+    "program",
+    "mailers",
+    "http-errors",
+    "ring",
+    "cache",
+    "crt-store",
+    "log-forward",
+    "traces",
+];
+
+// The sections where a `bind` may be a CLI.
+const PROXY_KEYWORDS: &[&str] = &["frontend", "listen"];
+
+// Resolves an environment variable, `None` if it is unset.
+type Env<'a> = &'a dyn Fn(&str) -> Option<String>;
+
+//
+/// A CLI declared in a haproxy.cfg, with `stats socket` in `global`
+/// or with a `bind ... level ...` in a proxy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsSocketConfig {
+    /// e.g. `global` or `listen cli`
+    pub section: Box<str>,
+    /// As written, after the variables resolution.
+    pub raw_address: Box<str>,
+    pub address: HaproxyAddress,
+    pub level: Option<CliLevel>,
+    /// Permissions of the unix socket, e.g. `0o600`.
+    pub mode: Option<u32>,
+    pub user: Option<Box<str>>,
+    pub group: Option<Box<str>>,
+    /// `expose-fd listeners`, needed for seamless reloads.
+    pub expose_fd_listeners: bool,
+}
+
+impl StatsSocketConfig {
+    /// Every declared CLI, an unsupported or invalid one doesn't fail the others.
+    pub fn from_config_file(
+        path: impl AsRef<Path>,
+    ) -> Result<Vec<Result<Self, StatsSocketConfigError>>, StatsSocketConfigError> {
+        let bytes = fs::read(path).map_err(StatsSocketConfigError::ReadFailed)?;
+        Ok(Self::from_config_bytes(bytes))
+    }
+
+    /// The variables are resolved from the environment.
+    pub fn from_config_bytes(bytes: impl AsRef<[u8]>) -> Vec<Result<Self, StatsSocketConfigError>> {
+        Self::from_config_bytes_with_env(bytes, |name| env::var(name).ok())
+    }
+
+    /// `$VAR`, `${VAR}`, `${VAR-default}` and `${VAR[*]}` are only resolved
+    /// in the `stats socket` and `bind` lines, as HAProxy does outside single quotes.
+    ///
+    /// HAProxy expands an unset one without default to nothing. Here the entry fails
+    /// with `StatsSocketConfigError::VariableUnset`, because the address would be wrong.
+    pub fn from_config_bytes_with_env(
+        bytes: impl AsRef<[u8]>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Vec<Result<Self, StatsSocketConfigError>> {
+        let s = String::from_utf8_lossy(bytes.as_ref());
+
+        let mut section = String::new();
+        let mut configs = vec![];
+
+        for line in s.lines() {
+            // Kept as written until the line is known to declare a CLI.
+            let words = split_words(line, None).unwrap_or_default();
+            let (keyword, args) = match words.split_first() {
+                Some((keyword, args)) => (keyword.as_str(), args),
+                None => continue,
+            };
+
+            if SECTION_KEYWORDS.contains(&keyword) {
+                section = words.join(" ");
+                continue;
+            }
+
+            let is_cli = match (keyword, args) {
+                ("stats", [socket, ..]) => socket == "socket" && section == "global",
+                ("bind", [_, params @ ..]) => {
+                    PROXY_KEYWORDS
+                        .iter()
+                        .any(|x| section.split(' ').next() == Some(x))
+                        && params.iter().any(|x| x == "level")
+                }
+                _ => false,
+            };
+            if !is_cli {
+                continue;
+            }
+
+            let words = match split_words(line, Some(&env)) {
+                Ok(words) => words,
+                Err(err) => {
+                    configs.push(Err(err));
+                    continue;
+                }
+            };
+            let (addresses, params) = match &words[..] {
+                [_, _, address, params @ ..] if keyword == "stats" => (address, params),
+                [_, address, params @ ..] if keyword == "bind" => (address, params),
+                _ => continue,
+            };
+
+            for raw_address in addresses.split(',') {
+                configs.push(Self::new(&section, raw_address, params));
+            }
+        }
+
+        configs
+    }
+
+    fn new(
+        section: &str,
+        raw_address: &str,
+        params: &[String],
+    ) -> Result<Self, StatsSocketConfigError> {
+        let mut config = Self {
+            section: section.into(),
+            raw_address: raw_address.into(),
+            address: raw_address
+                .parse()
+                .map_err(StatsSocketConfigError::AddressInvalid)?,
+            level: None,
+            mode: None,
+            user: None,
+            group: None,
+            expose_fd_listeners: false,
+        };
+
+        let mut params = params.iter().map(|x| x.as_str());
+        while let Some(param) = params.next() {
+            match (param, params.clone().next()) {
+                ("level", Some(value)) => {
+                    config.level = Some(
+                        value
+                            .parse()
+                            .map_err(StatsSocketConfigError::LevelInvalid)?,
+                    );
+                }
+                ("mode", Some(value)) => {
+                    config.mode = Some(
+                        u32::from_str_radix(value, 8)
+                            .map_err(|_| StatsSocketConfigError::ModeInvalid(value.into()))?,
+                    );
+                }
+                ("user", Some(value)) => config.user = Some(value.into()),
+                ("group", Some(value)) => config.group = Some(value.into()),
+                ("expose-fd", Some("listeners")) => config.expose_fd_listeners = true,
+                _ => continue,
+            }
+            params.next();
+        }
+
+        Ok(config)
+    }
+
+    /// With the declared level, if any.
+    pub fn client_builder(&self) -> ClientBuilder {
+        let builder = ClientBuilder::with_haproxy_address(self.address.clone());
+        match self.level {
+            Some(level) => builder.level(level),
+            None => builder,
+        }
+    }
+
+    pub fn client(&self) -> Client {
+        self.client_builder().build()
+    }
+}
+
+// Words separated by spaces, with quotes, escapes, comments and variables, as read by HAProxy.
+// Without `env`, the variables are kept as written and it doesn't fail.
+fn split_words(line: &str, env: Option<Env<'_>>) -> Result<Vec<String>, StatsSocketConfigError> {
+    let mut words = vec![];
+    let mut word: Option<String> = None;
+    let mut quote: Option<char> = None;
+
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', Some('\'')) => word.get_or_insert_with(String::new).push(c),
+            ('\\', _) => {
+                if let Some(c) = chars.next() {
+                    word.get_or_insert_with(String::new).push(c);
+                }
+            }
+            ('"' | '\'', None) => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (c, Some(q)) if c == q => quote = None,
+            ('$', q) if q != Some('\'') => {
+                let (raw, variable) = match chars.peek() {
+                    Some('{') => {
+                        chars.next();
+                        let mut inner = String::new();
+                        let mut closed = false;
+                        for x in chars.by_ref() {
+                            if x == '}' {
+                                closed = true;
+                                break;
+                            }
+                            inner.push(x);
+                        }
+                        let raw = match closed {
+                            true => format!("${{{}}}", inner),
+                            false => format!("${{{}", inner),
+                        };
+                        match (closed, env) {
+                            (_, None) => (raw, None),
+                            (true, _) => (raw, Some(Variable::parse(&inner)?)),
+                            (false, _) => {
+                                return Err(StatsSocketConfigError::VariableInvalid(raw.into()))
+                            }
+                        }
+                    }
+                    Some(x) if is_variable_name_char(x) => {
+                        let mut name = String::new();
+                        while let Some(x) = chars.next_if(is_variable_name_char) {
+                            name.push(x);
+                        }
+                        (format!("${}", name), Some(Variable::plain(name)))
+                    }
+                    _ => {
+                        word.get_or_insert_with(String::new).push(c);
+                        continue;
+                    }
+                };
+
+                let word = word.get_or_insert_with(String::new);
+                let (env, variable) = match (env, variable) {
+                    (Some(env), Some(variable)) => (env, variable),
+                    _ => {
+                        word.push_str(&raw);
+                        continue;
+                    }
+                };
+                let value = match (env(&variable.name), variable.default) {
+                    (Some(value), _) | (None, Some(value)) => value,
+                    (None, None) => {
+                        return Err(StatsSocketConfigError::VariableUnset(variable.name.into()))
+                    }
+                };
+                if variable.split {
+                    // Every value is a word.
+                    let mut values = value.split_whitespace();
+                    word.push_str(values.next().unwrap_or_default());
+                    for value in values {
+                        words.push(core::mem::take(word));
+                        word.push_str(value);
+                    }
+                } else {
+                    word.push_str(&value);
+                }
+            }
+            ('#', None) => break,
+            (c, None) if c.is_whitespace() => {
+                if let Some(word) = word.take() {
+                    words.push(word);
+                }
+            }
+            (c, _) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if let Some(word) = word {
+        words.push(word);
+    }
+
+    Ok(words)
+}
+
+// `${NAME}`, `${NAME-default}` or `${NAME[*]}`.
+struct Variable {
+    name: String,
+    default: Option<String>,
+    split: bool,
+}
+
+impl Variable {
+    fn plain(name: String) -> Self {
+        Self {
+            name,
+            default: None,
+            split: false,
+        }
+    }
+
+    fn parse(inner: &str) -> Result<Self, StatsSocketConfigError> {
+        let len = inner.chars().take_while(is_variable_name_char).count();
+        let (name, suffix) = inner.split_at(len);
+        let mut variable = Self::plain(name.into());
+        match suffix {
+            _ if name.is_empty() => {
+                return Err(StatsSocketConfigError::VariableInvalid(
+                    format!("${{{}}}", inner).into(),
+                ))
+            }
+            "" => {}
+            "[*]" => variable.split = true,
+            _ if suffix.starts_with('-') => variable.default = Some(suffix[1..].into()),
+            _ => {
+                return Err(StatsSocketConfigError::VariableInvalid(
+                    format!("${{{}}}", inner).into(),
+                ))
+            }
+        }
+        Ok(variable)
+    }
+}
+
+fn is_variable_name_char(x: &char) -> bool {
+    x.is_ascii_alphanumeric() || *x == '_'
+}
+
+//
+#[derive(Debug)]
+pub enum StatsSocketConfigError {
+    ReadFailed(IoError),
+    AddressInvalid(HaproxyAddressParseError),
+    LevelInvalid(CliLevelParseError),
+    ModeInvalid(Box<str>),
+    /// The name of a variable unset and without default.
+    VariableUnset(Box<str>),
+    /// e.g. `${PORT[0]}` or `${PORT`, as written.
+    VariableInvalid(Box<str>),
+}
+
+impl fmt::Display for StatsSocketConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for StatsSocketConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn test_stats_socket_config_from_docker_config() {
+        // The other variables are only used by the frontends.
+        let configs = StatsSocketConfig::from_config_bytes_with_env(
+            include_bytes!("../../haproxy_docker/with_stats_socket/conf/haproxy.cfg"),
+            env(&[("HAPROXY_STATS_SOCKET_PORT", "9999")]),
+        )
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+        assert_eq!(configs.len(), 2);
+
+        assert_eq!(configs[0].section.as_ref(), "global");
+        assert_eq!(
+            configs[0].address,
+            HaproxyAddress::Unix(Path::new("/var/run/haproxy.sock").into())
+        );
+        assert_eq!(configs[0].level, Some(CliLevel::Admin));
+        assert_eq!(configs[0].mode, Some(0o600));
+
+        assert_eq!(configs[1].raw_address.as_ref(), "ipv4@0.0.0.0:9999");
+        assert_eq!(
+            configs[1].address,
+            HaproxyAddress::Tcp(([127, 0, 0, 1], 9999).into())
+        );
+        assert_eq!(configs[1].level, Some(CliLevel::Admin));
+        assert_eq!(configs[1].mode, None);
+        assert!(!configs[1].expose_fd_listeners);
+    }
+
+    #[test]
+    fn test_stats_socket_config_with_bind() {
+        let configs = StatsSocketConfig::from_config_bytes_with_env(
+            br#"
+global
+    # stats socket /var/run/commented.sock
+    stats socket "/run/haproxy/admin.sock" mode 660 level admin user haproxy group haproxy expose-fd listeners
+    stats socket abns@haproxy-cli level operator # comment
+
+defaults
+    mode http
+
+listen cli
+    bind 127.0.0.1:${CLI_PORT},ipv6@[::1]:${CLI_PORT} level user
+    bind :8080
+
+frontend http
+    bind :80
+"#,
+            env(&[("CLI_PORT", "9300")]),
+        )
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+        assert_eq!(configs.len(), 4);
+
+        assert_eq!(configs[0].raw_address.as_ref(), "/run/haproxy/admin.sock");
+        assert_eq!(configs[0].mode, Some(0o660));
+        assert_eq!(configs[0].user.as_deref(), Some("haproxy"));
+        assert_eq!(configs[0].group.as_deref(), Some("haproxy"));
+        assert!(configs[0].expose_fd_listeners);
+
+        assert_eq!(configs[1].raw_address.as_ref(), "abns@haproxy-cli");
+        assert_eq!(configs[1].level, Some(CliLevel::Operator));
+
+        assert_eq!(configs[2].section.as_ref(), "listen cli");
+        assert_eq!(
+            configs[2].address,
+            HaproxyAddress::Tcp(([127, 0, 0, 1], 9300).into())
+        );
+        assert_eq!(configs[2].level, Some(CliLevel::User));
+        assert_eq!(
+            configs[3].address,
+            HaproxyAddress::Tcp("[::1]:9300".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_stats_socket_config_with_unsupported_address() {
+        let configs = StatsSocketConfig::from_config_bytes_with_env(
+            r#"
+global
+    stats socket fd@3 level admin
+    stats socket ipv4@127.0.0.1:${UNSET_PORT} level admin
+    stats socket /var/run/haproxy.sock level admin
+
+listen cli
+    bind 127.0.0.1:9300-9301,127.0.0.1:9302 level user
+"#,
+            |_| None,
+        );
+        assert_eq!(configs.len(), 5);
+
+        match &configs[0] {
+            Err(StatsSocketConfigError::AddressInvalid(
+                HaproxyAddressParseError::PrefixUnsupported(prefix),
+            )) => assert_eq!(prefix.as_ref(), "fd"),
+            x => panic!("{:?}", x),
+        }
+        match &configs[1] {
+            Err(StatsSocketConfigError::VariableUnset(name)) => {
+                assert_eq!(name.as_ref(), "UNSET_PORT")
+            }
+            x => panic!("{:?}", x),
+        }
+        assert_eq!(
+            configs[2].as_ref().unwrap().address,
+            HaproxyAddress::Unix(Path::new("/var/run/haproxy.sock").into())
+        );
+        assert!(configs[3].is_err());
+        assert_eq!(
+            configs[4].as_ref().unwrap().address,
+            HaproxyAddress::Tcp(([127, 0, 0, 1], 9302).into())
+        );
+    }
+
+    #[test]
+    fn test_stats_socket_config_with_variables() {
+        let configs = StatsSocketConfig::from_config_bytes_with_env(
+            r#"
+crt-store web
+    load crt "site.pem"
+
+global
+    stats socket "$RUN_DIR/haproxy.sock" level ${LEVEL-operator}
+    stats socket ipv4@127.0.0.1:${PORT-9301} level admin
+    stats socket '/run/$RUN_DIR.sock' level admin
+    stats socket ${SOCKET[*]}
+
+log-forward syslog
+    bind 127.0.0.1:514 level admin
+
+traces
+    trace h1 sink stderr
+"#,
+            env(&[
+                ("RUN_DIR", "/run/haproxy"),
+                ("PORT", "9300"),
+                ("SOCKET", "ipv4@127.0.0.1:9302  level user"),
+            ]),
+        )
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+        assert_eq!(configs.len(), 4);
+
+        assert_eq!(configs[0].raw_address.as_ref(), "/run/haproxy/haproxy.sock");
+        assert_eq!(configs[0].level, Some(CliLevel::Operator));
+        assert_eq!(
+            configs[1].address,
+            HaproxyAddress::Tcp(([127, 0, 0, 1], 9300).into())
+        );
+        // Not resolved in single quotes.
+        assert_eq!(configs[2].raw_address.as_ref(), "/run/$RUN_DIR.sock");
+        assert_eq!(configs[3].raw_address.as_ref(), "ipv4@127.0.0.1:9302");
+        assert_eq!(configs[3].level, Some(CliLevel::User));
+
+        // The `bind` of `log-forward` is not a CLI.
+        assert!(configs.iter().all(|x| x.section.as_ref() == "global"));
+    }
+
+    #[test]
+    fn test_stats_socket_config_with_invalid_variable() {
+        let configs = StatsSocketConfig::from_config_bytes_with_env(
+            "global\n    stats socket ${PORT[0]} level admin\n    stats socket ${PORT level admin\n",
+            |_| Some("9300".to_owned()),
+        );
+        assert_eq!(configs.len(), 2);
+        match &configs[0] {
+            Err(StatsSocketConfigError::VariableInvalid(x)) => assert_eq!(x.as_ref(), "${PORT[0]}"),
+            x => panic!("{:?}", x),
+        }
+        match &configs[1] {
+            Err(StatsSocketConfigError::VariableInvalid(x)) => {
+                assert_eq!(x.as_ref(), "${PORT level admin")
+            }
+            x => panic!("{:?}", x),
+        }
+    }
+}
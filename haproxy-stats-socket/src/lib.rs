@@ -1,6 +1,35 @@
+#[cfg(feature = "_async")]
+pub use futures_io;
 pub use haproxy_stats;
+#[cfg(feature = "tls")]
+pub use rustls;
 
 //
+pub mod access;
+pub mod address;
 pub mod client;
+pub mod client_set;
+pub mod discovery;
+pub mod master;
+#[cfg(feature = "_async")]
+pub mod pool;
+#[cfg(feature = "_async")]
+pub mod session;
+#[cfg(feature = "tls")]
+pub mod tls;
+mod trace;
+pub mod transport;
 
-pub use client::Client;
+pub use access::{AdminClient, ReadOnlyClient};
+pub use address::HaproxyAddress;
+pub use client::{Client, ClientBuilder};
+pub use client_set::ClientSet;
+pub use discovery::StatsSocketConfig;
+pub use master::{MasterClient, ProcessTarget, ReloadOptions, ReloadOutcome};
+#[cfg(feature = "_async")]
+pub use pool::{Pool, PoolBuilder};
+#[cfg(feature = "_async")]
+pub use session::Session;
+#[cfg(feature = "tls")]
+pub use tls::{TlsOptions, TlsTransport};
+pub use transport::{TcpTransport, Transport, UnixTransport};
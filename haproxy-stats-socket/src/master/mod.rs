@@ -0,0 +1,123 @@
+use core::fmt;
+#[cfg(feature = "_async")]
+use core::future::Future;
+use std::{net::SocketAddr, path::Path};
+
+use haproxy_stats::{Process, Processes};
+
+use crate::{
+    client::{Client, ClientShowProcError},
+    transport::Transport,
+};
+
+//
+mod reload;
+
+pub use reload::{MasterClientReloadError, ReloadOptions, ReloadOutcome};
+
+//
+/// Where the master CLI routes a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProcessTarget {
+    /// `@master`
+    Master,
+    /// `@<relative pid>`, e.g. `@1` for the current worker.
+    Relative(u32),
+    /// `@!<pid>`, also reaches the old workers.
+    Pid(u32),
+}
+
+impl fmt::Display for ProcessTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Master => write!(f, "@master"),
+            Self::Relative(n) => write!(f, "@{}", n),
+            Self::Pid(pid) => write!(f, "@!{}", pid),
+        }
+    }
+}
+
+//
+/// Client of the master CLI in master-worker mode, see `stats socket ... master` or `-S`.
+#[derive(Debug, Clone)]
+pub struct MasterClient {
+    client: Client,
+}
+
+impl MasterClient {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    pub fn with_tcp(addr: impl Into<SocketAddr>) -> Self {
+        Self::new(Client::with_tcp(addr))
+    }
+
+    pub fn with_unix(path: impl AsRef<Path>) -> Self {
+        Self::new(Client::with_unix(path))
+    }
+
+    pub fn with_transport(transport: impl Transport) -> Self {
+        Self::new(Client::with_transport(transport))
+    }
+
+    /// Commands sent with it are handled by the master itself.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Every command sent with the returned client is routed to the target,
+    /// e.g. `master.route(ProcessTarget::Relative(1)).show_stat()`.
+    pub fn route(&self, target: ProcessTarget) -> Client {
+        self.client.with_target(target)
+    }
+
+    /// The current workers, with a client routed to each of them by pid.
+    #[cfg(feature = "_async")]
+    pub async fn workers(&self) -> Result<Vec<(Process, Client)>, ClientShowProcError> {
+        Ok(self.route_workers(self.show_proc().await?))
+    }
+
+    /// Calls `f` for every current worker, one after another.
+    #[cfg(feature = "_async")]
+    pub async fn for_each_worker<T, F, Fut>(
+        &self,
+        f: F,
+    ) -> Result<Vec<(Process, T)>, ClientShowProcError>
+    where
+        F: Fn(Client) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let mut ret = vec![];
+        for (process, client) in self.workers().await? {
+            ret.push((process, f(client).await));
+        }
+        Ok(ret)
+    }
+
+    pub fn workers_blocking(&self) -> Result<Vec<(Process, Client)>, ClientShowProcError> {
+        Ok(self.route_workers(self.show_proc_blocking()?))
+    }
+
+    pub fn for_each_worker_blocking<T>(
+        &self,
+        f: impl Fn(Client) -> T,
+    ) -> Result<Vec<(Process, T)>, ClientShowProcError> {
+        Ok(self
+            .workers_blocking()?
+            .into_iter()
+            .map(|(process, client)| (process, f(client)))
+            .collect())
+    }
+
+    fn route_workers(&self, processes: Processes) -> Vec<(Process, Client)> {
+        processes
+            .workers
+            .into_iter()
+            .map(|x| {
+                let client = self.route(ProcessTarget::Pid(x.pid));
+                (x, client)
+            })
+            .collect()
+    }
+}
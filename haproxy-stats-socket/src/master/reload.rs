@@ -0,0 +1,248 @@
+use core::{fmt, time::Duration};
+use std::{thread, time::Instant};
+
+use haproxy_stats::{CliError, Command, Process, Processes, ReloadStatus};
+
+use super::MasterClient;
+use crate::client::{ClientSendError, ClientShowProcError};
+
+//
+/// Options of `MasterClient::reload`.
+#[derive(Debug, Clone)]
+pub struct ReloadOptions {
+    timeout: Duration,
+    drain_timeout: Duration,
+    poll_interval: Duration,
+}
+
+impl Default for ReloadOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            drain_timeout: Duration::from_secs(0),
+            poll_interval: Duration::from_millis(500),
+        }
+    }
+}
+
+impl ReloadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Until a new worker is up.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Until the old workers exit, after a new worker is up.
+    pub fn drain_timeout(mut self, timeout: Duration) -> Self {
+        self.drain_timeout = timeout;
+        self
+    }
+
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+}
+
+//
+#[derive(Debug, Clone)]
+pub enum ReloadOutcome {
+    Success {
+        workers: Vec<Process>,
+        /// Old workers still serving their last connections after the drain timeout.
+        leaving_workers: Vec<Process>,
+    },
+    Failed {
+        /// Only since 2.7.
+        startup_logs: Option<Box<str>>,
+    },
+    /// No new worker is up in time.
+    Timeout { processes: Option<Processes> },
+}
+
+impl ReloadOutcome {
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success { .. })
+    }
+}
+
+//
+impl MasterClient {
+    /// Sends `reload`, then polls `show proc` until a new worker is up.
+    ///
+    /// Before 2.7 the master closes the connection without a status,
+    /// a failed reload is detected by the `[failed: N]` counter since 2.5, else ends with a timeout.
+    #[cfg(feature = "_async")]
+    pub async fn reload(
+        &self,
+        options: &ReloadOptions,
+    ) -> Result<ReloadOutcome, MasterClientReloadError> {
+        let before = self
+            .show_proc()
+            .await
+            .map_err(MasterClientReloadError::ShowProcFailed)?;
+        let mut verifier = ReloadVerifier::new(before);
+
+        let response = self.client().send_async(&Command::reload()).await;
+        if let Some(outcome) = verifier.on_reload_response(response)? {
+            return Ok(outcome);
+        }
+
+        loop {
+            self.client()
+                .options()
+                .runtime
+                .sleep(options.poll_interval)
+                .await;
+
+            if let Some(outcome) = verifier.on_show_proc(self.show_proc().await, options) {
+                return Ok(outcome);
+            }
+        }
+    }
+
+    pub fn reload_blocking(
+        &self,
+        options: &ReloadOptions,
+    ) -> Result<ReloadOutcome, MasterClientReloadError> {
+        let before = self
+            .show_proc_blocking()
+            .map_err(MasterClientReloadError::ShowProcFailed)?;
+        let mut verifier = ReloadVerifier::new(before);
+
+        let response = self.client().send(&Command::reload());
+        if let Some(outcome) = verifier.on_reload_response(response)? {
+            return Ok(outcome);
+        }
+
+        loop {
+            thread::sleep(options.poll_interval);
+
+            if let Some(outcome) = verifier.on_show_proc(self.show_proc_blocking(), options) {
+                return Ok(outcome);
+            }
+        }
+    }
+}
+
+//
+// Shared by the async and the blocking reload.
+struct ReloadVerifier {
+    started_at: Instant,
+    old_pids: Vec<u32>,
+    failed_reloads: Option<u64>,
+    last: Option<Processes>,
+    // Set once a new worker is up.
+    workers: Option<(Vec<Process>, Instant)>,
+}
+
+impl ReloadVerifier {
+    fn new(before: Processes) -> Self {
+        Self {
+            started_at: Instant::now(),
+            old_pids: before.worker_pids(),
+            failed_reloads: before.master.as_ref().and_then(|x| x.failed_reloads),
+            last: None,
+            workers: None,
+        }
+    }
+
+    fn on_reload_response(
+        &self,
+        response: Result<Vec<u8>, ClientSendError>,
+    ) -> Result<Option<ReloadOutcome>, MasterClientReloadError> {
+        match response {
+            Ok(response) => {
+                if let Some(err) = CliError::from_response_bytes(&response) {
+                    return Err(MasterClientReloadError::CliError(err));
+                }
+                match ReloadStatus::from_reload_bytes(response) {
+                    Ok(status) if !status.success => Ok(Some(ReloadOutcome::Failed {
+                        startup_logs: Some(status.startup_logs),
+                    })),
+                    _ => Ok(None),
+                }
+            }
+            // The master re-executes itself.
+            Err(ClientSendError::ReadFailed(_)) => Ok(None),
+            Err(err) => Err(MasterClientReloadError::ReloadSendFailed(err)),
+        }
+    }
+
+    fn on_show_proc(
+        &mut self,
+        processes: Result<Processes, ClientShowProcError>,
+        options: &ReloadOptions,
+    ) -> Option<ReloadOutcome> {
+        if let Some((workers, drain_started_at)) = &self.workers {
+            let leaving_workers = match processes {
+                Ok(processes) => self.leaving_workers(processes),
+                Err(_) => vec![],
+            };
+
+            if leaving_workers.is_empty() || drain_started_at.elapsed() >= options.drain_timeout {
+                return Some(ReloadOutcome::Success {
+                    workers: workers.to_owned(),
+                    leaving_workers,
+                });
+            }
+            return None;
+        }
+
+        // The master CLI is unavailable while it is re-executing.
+        if let Ok(processes) = processes {
+            if self.failed_reloads.is_some()
+                && processes.master.as_ref().and_then(|x| x.failed_reloads) > self.failed_reloads
+            {
+                return Some(ReloadOutcome::Failed { startup_logs: None });
+            }
+
+            if processes
+                .workers
+                .iter()
+                .any(|x| !self.old_pids.contains(&x.pid))
+            {
+                self.workers = Some((processes.workers.to_owned(), Instant::now()));
+                return self.on_show_proc(Ok(processes), options);
+            }
+
+            self.last = Some(processes);
+        }
+
+        if self.started_at.elapsed() >= options.timeout {
+            return Some(ReloadOutcome::Timeout {
+                processes: self.last.take(),
+            });
+        }
+
+        None
+    }
+
+    fn leaving_workers(&self, processes: Processes) -> Vec<Process> {
+        processes
+            .old_workers
+            .into_iter()
+            .filter(|x| self.old_pids.contains(&x.pid))
+            .collect()
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum MasterClientReloadError {
+    ShowProcFailed(ClientShowProcError),
+    ReloadSendFailed(ClientSendError),
+    CliError(CliError),
+}
+
+impl fmt::Display for MasterClientReloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for MasterClientReloadError {}
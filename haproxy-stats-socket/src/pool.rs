@@ -0,0 +1,225 @@
+use core::{
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use async_lock::{Semaphore, SemaphoreGuardArc};
+use haproxy_stats::{Command, Commands};
+
+use crate::{
+    client::{Client, ClientSendError},
+    session::Session,
+};
+
+//
+const DEFAULT_MAX_SIZE: usize = 4;
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+//
+/// Bounded pool of interactive sessions, it works with any of the async runtimes.
+///
+/// Callers waiting for a session are served in FIFO order.
+#[derive(Debug, Clone)]
+pub struct Pool {
+    inner: Arc<PoolInner>,
+}
+
+#[derive(Debug)]
+struct PoolInner {
+    client: Client,
+    semaphore: Arc<Semaphore>,
+    idle: Mutex<VecDeque<IdleSession>>,
+    max_size: usize,
+    idle_timeout: Duration,
+    health_check_interval: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct IdleSession {
+    session: Session,
+    idle_since: Instant,
+}
+
+impl Pool {
+    pub fn new(client: Client) -> Self {
+        PoolBuilder::new(client).build()
+    }
+
+    pub fn builder(client: Client) -> PoolBuilder {
+        PoolBuilder::new(client)
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.inner.client
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.inner.max_size
+    }
+
+    pub fn idle_count(&self) -> usize {
+        self.inner.idle.lock().expect("").len()
+    }
+
+    /// Waits for a free slot, then reuses an idle session or creates one.
+    pub async fn get(&self) -> PooledSession {
+        let permit = self.inner.semaphore.acquire_arc().await;
+
+        loop {
+            let idle = self.take_idle();
+
+            let mut session = match idle {
+                Some(idle) => match self.inner.health_check_interval {
+                    Some(interval) if idle.idle_since.elapsed() >= interval => {
+                        let mut session = idle.session;
+                        if session.send(&Command::new("").expect("")).await.is_err() {
+                            continue;
+                        }
+                        session
+                    }
+                    _ => idle.session,
+                },
+                None => self.inner.client.session(),
+            };
+
+            if !session.is_connected() {
+                session = self.inner.client.session();
+            }
+
+            return PooledSession {
+                session: Some(session),
+                pool: self.inner.clone(),
+                _permit: permit,
+            };
+        }
+    }
+
+    pub async fn send(&self, command: &Command) -> Result<Vec<u8>, ClientSendError> {
+        self.get().await.send(command).await
+    }
+
+    pub async fn send_multiple(
+        &self,
+        commands: Commands<'_>,
+    ) -> Result<Vec<Vec<u8>>, ClientSendError> {
+        self.get().await.send_multiple(commands).await
+    }
+
+    /// Drops the idle sessions which were idle for longer than the idle timeout.
+    pub fn evict_idle(&self) {
+        let idle_timeout = self.inner.idle_timeout;
+        self.inner
+            .idle
+            .lock()
+            .expect("")
+            .retain(|x| x.idle_since.elapsed() < idle_timeout);
+    }
+
+    fn take_idle(&self) -> Option<IdleSession> {
+        self.evict_idle();
+        self.inner.idle.lock().expect("").pop_back()
+    }
+}
+
+//
+#[derive(Debug)]
+pub struct PoolBuilder {
+    client: Client,
+    max_size: usize,
+    idle_timeout: Duration,
+    health_check_interval: Option<Duration>,
+}
+
+impl PoolBuilder {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            max_size: DEFAULT_MAX_SIZE,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            health_check_interval: Some(DEFAULT_HEALTH_CHECK_INTERVAL),
+        }
+    }
+
+    /// Keep it below the `maxconn` of the stats socket.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size.max(1);
+        self
+    }
+
+    /// Keep it below the CLI timeout (`stats timeout`, 10s by default).
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// Sessions idle for longer are checked with an empty command before reuse.
+    pub fn health_check_interval(mut self, interval: Option<Duration>) -> Self {
+        self.health_check_interval = interval;
+        self
+    }
+
+    pub fn build(self) -> Pool {
+        Pool {
+            inner: Arc::new(PoolInner {
+                client: self.client,
+                semaphore: Arc::new(Semaphore::new(self.max_size)),
+                idle: Mutex::new(VecDeque::with_capacity(self.max_size)),
+                max_size: self.max_size,
+                idle_timeout: self.idle_timeout,
+                health_check_interval: self.health_check_interval,
+            }),
+        }
+    }
+}
+
+//
+/// Returns the session to the pool on drop, unless it was disconnected,
+/// e.g. a send was cancelled before the whole response was read.
+#[derive(Debug)]
+pub struct PooledSession {
+    session: Option<Session>,
+    pool: Arc<PoolInner>,
+    _permit: SemaphoreGuardArc,
+}
+
+impl Deref for PooledSession {
+    type Target = Session;
+
+    fn deref(&self) -> &Self::Target {
+        self.session.as_ref().expect("")
+    }
+}
+
+impl DerefMut for PooledSession {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.session.as_mut().expect("")
+    }
+}
+
+impl Drop for PooledSession {
+    fn drop(&mut self) {
+        if let Some(session) = self.session.take() {
+            if session.is_connected() {
+                if let Ok(mut idle) = self.pool.idle.lock() {
+                    idle.push_back(IdleSession {
+                        session,
+                        idle_since: Instant::now(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+//
+impl Client {
+    pub fn pool(&self, max_size: usize) -> Pool {
+        PoolBuilder::new(self.clone()).max_size(max_size).build()
+    }
+}
@@ -0,0 +1,220 @@
+use core::time::Duration;
+use std::time::Instant;
+
+use haproxy_stats::{CliMode, Command, Commands};
+
+use crate::{
+    client::{
+        io::{read_async, unexpected_eof, write_async, AsyncStream, Deadline, ReadUntil},
+        prompt::Prompt,
+        Client, ClientSendError,
+    },
+    trace::SendSpan,
+};
+
+//
+/// Interactive (prompt mode) session, keeps the connection open between commands.
+///
+/// When the connection was closed by HAProxy, e.g. after the CLI timeout,
+/// it reconnects and sends the command again if it could not be written,
+/// or if it is idempotent and nothing of the response was received.
+/// A send cancelled before the whole response was read leaves it disconnected.
+#[derive(Debug)]
+pub struct Session {
+    client: Client,
+    // With the prompt learned when connecting.
+    stream: Option<(AsyncStream, Prompt)>,
+    timeout_cli: Option<Duration>,
+    modes: Vec<CliMode>,
+    last_active_at: Instant,
+}
+
+impl Session {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            stream: None,
+            timeout_cli: None,
+            modes: vec![],
+            last_active_at: Instant::now(),
+        }
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Sends `set timeout cli`, it is sent again after reconnecting.
+    pub async fn set_timeout_cli(&mut self, timeout: Duration) -> Result<(), ClientSendError> {
+        self.timeout_cli = Some(timeout);
+
+        if self.stream.is_some() {
+            let command = Command::set_timeout_cli(timeout);
+            self.send_command(&command, command.to_write_bytes())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends `expert-mode on|off` or `experimental-mode on|off`,
+    /// the modes turned on are sent again after reconnecting.
+    pub async fn set_mode(&mut self, mode: CliMode, on: bool) -> Result<(), ClientSendError> {
+        self.modes.retain(|x| *x != mode);
+        if on {
+            self.modes.push(mode);
+        }
+
+        if self.stream.is_some() {
+            let command = match mode {
+                CliMode::Expert => Command::expert_mode(on),
+                CliMode::Experimental => Command::experimental_mode(on),
+            };
+            self.send_command(&command, command.to_write_bytes())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    pub fn modes(&self) -> &[CliMode] {
+        &self.modes
+    }
+
+    pub async fn send(&mut self, command: &Command) -> Result<Vec<u8>, ClientSendError> {
+        self.client.options().check_level(command)?;
+        for mode in command.required_modes() {
+            if !self.modes.contains(&mode) {
+                self.set_mode(mode, true).await?;
+            }
+        }
+        let span = SendSpan::new([command], self.client.transport());
+        let write_bytes = self.client.options().to_write_bytes(command);
+        span.instrument(self.send_command(command, write_bytes))
+            .await
+    }
+
+    pub async fn send_multiple(
+        &mut self,
+        commands: Commands<'_>,
+    ) -> Result<Vec<Vec<u8>>, ClientSendError> {
+        let mut responses = Vec::with_capacity(commands.0.len());
+        for command in commands.0 {
+            responses.push(self.send(command).await?);
+        }
+        Ok(responses)
+    }
+
+    // `write_bytes` is the command, with the target if any.
+    async fn send_command(
+        &mut self,
+        command: &Command,
+        write_bytes: Vec<u8>,
+    ) -> Result<Vec<u8>, ClientSendError> {
+        let options = self.client.options().clone();
+        let deadline = options.new_deadline();
+
+        let mut reconnected = false;
+        loop {
+            if self.is_expired() {
+                self.stream = None;
+            }
+
+            // Taken for the exchange, if it is cancelled, the session is left disconnected
+            // instead of with the rest of the response unread.
+            let (mut stream, prompt) = match self.stream.take() {
+                Some(x) => x,
+                None => {
+                    reconnected = true;
+                    self.connect(deadline).await?
+                }
+            };
+
+            // Not written, the command can be sent again.
+            if let Err(err) = write_async(&mut stream, &write_bytes[..], &options, deadline).await {
+                match err {
+                    ClientSendError::WriteFailed(_) if !reconnected => continue,
+                    err => return Err(err),
+                }
+            }
+
+            // Written, it may have been run before the connection was closed.
+            match read_async(&mut stream, ReadUntil::Prompt(&prompt), &options, deadline).await {
+                Ok(Some(response)) => {
+                    self.last_active_at = Instant::now();
+                    self.stream = Some((stream, prompt));
+                    return Ok(response);
+                }
+                Ok(None) if !reconnected && command.is_idempotent() => continue,
+                Ok(None) => return Err(unexpected_eof()),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    pub async fn close(&mut self) -> Result<(), ClientSendError> {
+        if let Some((mut stream, _)) = self.stream.take() {
+            let deadline = self.client.options().new_deadline();
+            write_async(
+                &mut stream,
+                &Command::quit().to_write_bytes()[..],
+                self.client.options(),
+                deadline,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn connect(
+        &mut self,
+        deadline: Deadline,
+    ) -> Result<(AsyncStream, Prompt), ClientSendError> {
+        let (mut stream, prompt) = self.client.connect_prompt_async(&[], deadline).await?;
+
+        let commands = self
+            .timeout_cli
+            .map(Command::set_timeout_cli)
+            .into_iter()
+            .chain(self.modes.iter().map(|x| x.to_command()));
+        for command in commands {
+            write_async(
+                &mut stream,
+                &command.to_write_bytes()[..],
+                self.client.options(),
+                deadline,
+            )
+            .await?;
+            read_async(
+                &mut stream,
+                ReadUntil::Prompt(&prompt),
+                self.client.options(),
+                deadline,
+            )
+            .await?
+            .ok_or_else(unexpected_eof)?;
+        }
+
+        self.last_active_at = Instant::now();
+
+        Ok((stream, prompt))
+    }
+
+    fn is_expired(&self) -> bool {
+        match self.timeout_cli {
+            Some(timeout) => self.last_active_at.elapsed() >= timeout,
+            None => false,
+        }
+    }
+}
+
+//
+impl Client {
+    pub fn session(&self) -> Session {
+        Session::new(self.clone())
+    }
+}
@@ -0,0 +1,221 @@
+use core::{fmt, time::Duration};
+use std::{
+    io::{Error as IoError, ErrorKind as IoErrorKind},
+    net::{SocketAddr, TcpStream},
+    sync::Arc,
+};
+
+use rustls::{
+    crypto::ring,
+    pki_types::{
+        pem::{Error as PemError, PemObject as _},
+        CertificateDer, InvalidDnsNameError, PrivateKeyDer, ServerName,
+    },
+    ClientConfig, ClientConnection, Error as RustlsError, RootCertStore, StreamOwned,
+};
+
+#[cfg(feature = "_async")]
+use crate::{
+    client::AsyncRuntime,
+    transport::{AsyncStream, ConnectFuture},
+};
+use crate::{
+    client::{Client, ClientBuilder},
+    transport::{BlockingReadWrite, BlockingStream, Transport},
+};
+
+//
+/// TLS settings of a CLI behind a TLS terminator, e.g. stunnel or `bind ... ssl`.
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptions {
+    ca_pem: Vec<u8>,
+    client_auth_pem: Option<(Vec<u8>, Vec<u8>)>,
+    server_name: Option<Box<str>>,
+}
+
+impl TlsOptions {
+    /// PEM encoded CA certificates trusted for the server certificate, required.
+    pub fn ca_pem(mut self, pem: impl AsRef<[u8]>) -> Self {
+        self.ca_pem.extend_from_slice(pem.as_ref());
+        self.ca_pem.push(b'\n');
+        self
+    }
+
+    /// PEM encoded client certificate chain and private key.
+    pub fn client_auth_pem(
+        mut self,
+        cert_pem: impl AsRef<[u8]>,
+        key_pem: impl AsRef<[u8]>,
+    ) -> Self {
+        self.client_auth_pem = Some((cert_pem.as_ref().to_vec(), key_pem.as_ref().to_vec()));
+        self
+    }
+
+    /// SNI and the name verified in the server certificate, defaults to the IP address.
+    pub fn server_name(mut self, name: impl AsRef<str>) -> Self {
+        self.server_name = Some(name.as_ref().into());
+        self
+    }
+
+    pub fn to_client_config(&self) -> Result<ClientConfig, TlsTransportError> {
+        let mut roots = RootCertStore::empty();
+        for cert in CertificateDer::pem_slice_iter(&self.ca_pem) {
+            roots
+                .add(cert.map_err(TlsTransportError::PemInvalid)?)
+                .map_err(TlsTransportError::ConfigInvalid)?;
+        }
+        if roots.is_empty() {
+            return Err(TlsTransportError::CaMissing);
+        }
+
+        let builder = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(TlsTransportError::ConfigInvalid)?
+            .with_root_certificates(roots);
+
+        match &self.client_auth_pem {
+            Some((cert_pem, key_pem)) => {
+                let certs = CertificateDer::pem_slice_iter(cert_pem)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(TlsTransportError::PemInvalid)?;
+                let key = PrivateKeyDer::from_pem_slice(key_pem)
+                    .map_err(TlsTransportError::PemInvalid)?;
+                builder
+                    .with_client_auth_cert(certs, key)
+                    .map_err(TlsTransportError::ConfigInvalid)
+            }
+            None => Ok(builder.with_no_client_auth()),
+        }
+    }
+}
+
+//
+/// TCP, then TLS. The handshake is part of connecting.
+#[derive(Clone)]
+pub struct TlsTransport {
+    addr: SocketAddr,
+    server_name: ServerName<'static>,
+    config: Arc<ClientConfig>,
+}
+
+// Without the config, it is logged as the target of every send.
+impl fmt::Debug for TlsTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsTransport")
+            .field("addr", &self.addr)
+            .field("server_name", &self.server_name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TlsTransport {
+    pub fn new(
+        addr: impl Into<SocketAddr>,
+        options: &TlsOptions,
+    ) -> Result<Self, TlsTransportError> {
+        let addr = addr.into();
+        let server_name = match &options.server_name {
+            Some(name) => ServerName::try_from(name.as_ref())
+                .map_err(TlsTransportError::ServerNameInvalid)?
+                .to_owned(),
+            None => ServerName::IpAddress(addr.ip().into()),
+        };
+
+        Ok(Self::with_config(
+            addr,
+            server_name,
+            Arc::new(options.to_client_config()?),
+        ))
+    }
+
+    pub fn with_config(
+        addr: impl Into<SocketAddr>,
+        server_name: ServerName<'static>,
+        config: Arc<ClientConfig>,
+    ) -> Self {
+        Self {
+            addr: addr.into(),
+            server_name,
+            config,
+        }
+    }
+}
+
+impl Transport for TlsTransport {
+    fn connect(&self, timeout: Option<Duration>) -> Result<BlockingStream, IoError> {
+        let mut sock = match timeout {
+            Some(timeout) => TcpStream::connect_timeout(&self.addr, timeout)?,
+            None => TcpStream::connect(self.addr)?,
+        };
+
+        let mut conn = ClientConnection::new(self.config.clone(), self.server_name.clone())
+            .map_err(|err| IoError::new(IoErrorKind::InvalidData, err))?;
+
+        sock.set_read_timeout(timeout)?;
+        sock.set_write_timeout(timeout)?;
+        while conn.is_handshaking() {
+            conn.complete_io(&mut sock)?;
+        }
+
+        Ok(Box::new(StreamOwned::new(conn, sock)))
+    }
+
+    #[cfg(feature = "_async")]
+    fn connect_async(&self, runtime: AsyncRuntime) -> ConnectFuture<'_> {
+        Box::pin(async move {
+            let sock = runtime.connect_tcp(self.addr).await?;
+
+            let stream = futures_rustls::TlsConnector::from(self.config.clone())
+                .connect(self.server_name.clone(), sock)
+                .await?;
+
+            Ok(Box::new(stream) as AsyncStream)
+        })
+    }
+}
+
+impl BlockingReadWrite for StreamOwned<ClientConnection, TcpStream> {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), IoError> {
+        self.sock.set_read_timeout(timeout)
+    }
+
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> Result<(), IoError> {
+        self.sock.set_write_timeout(timeout)
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum TlsTransportError {
+    CaMissing,
+    PemInvalid(PemError),
+    ServerNameInvalid(InvalidDnsNameError),
+    ConfigInvalid(RustlsError),
+}
+
+impl fmt::Display for TlsTransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for TlsTransportError {}
+
+//
+impl Client {
+    pub fn with_tls(
+        addr: impl Into<SocketAddr>,
+        options: &TlsOptions,
+    ) -> Result<Self, TlsTransportError> {
+        Ok(Self::with_transport(TlsTransport::new(addr, options)?))
+    }
+
+    pub fn builder_with_tls(
+        addr: impl Into<SocketAddr>,
+        options: &TlsOptions,
+    ) -> Result<ClientBuilder, TlsTransportError> {
+        Ok(ClientBuilder::with_transport(TlsTransport::new(
+            addr, options,
+        )?))
+    }
+}
@@ -0,0 +1,191 @@
+use core::fmt;
+#[cfg(feature = "_async")]
+use core::future::Future;
+#[cfg(feature = "tracing")]
+use core::time::Duration;
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+use haproxy_stats::Command;
+
+use crate::{client::ClientSendError, transport::Transport};
+
+// With the `tracing` feature, every send is in a `haproxy_stats_socket.send` span
+// with the redacted command and the target, connect, write, read and parse are events
+// with the byte count, the duration and the error. Without it, all are no-ops.
+
+//
+/// Covers a send, with its retries.
+#[derive(Debug, Clone)]
+pub(crate) struct SendSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl SendSpan {
+    pub(crate) fn new<'a>(
+        commands: impl IntoIterator<Item = &'a Command>,
+        transport: &dyn Transport,
+    ) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::debug_span!(
+                "haproxy_stats_socket.send",
+                command = tracing::field::Empty,
+                target = ?transport,
+            );
+            if !span.is_disabled() {
+                let command = commands
+                    .into_iter()
+                    .map(|x| x.to_redacted_string())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                span.record("command", command.as_str());
+            }
+            Self { span }
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = (commands, transport);
+            Self {}
+        }
+    }
+
+    pub(crate) fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.in_scope(f)
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            f()
+        }
+    }
+
+    #[cfg(feature = "_async")]
+    pub(crate) async fn instrument<T>(&self, future: impl Future<Output = T>) -> T {
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Instrument::instrument(future, self.span.clone()).await
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            future.await
+        }
+    }
+}
+
+//
+/// One of connect, write, read or parse.
+#[derive(Debug)]
+pub(crate) struct Step {
+    #[cfg(feature = "tracing")]
+    name: &'static str,
+    #[cfg(feature = "tracing")]
+    started_at: Instant,
+}
+
+impl Step {
+    pub(crate) fn start(name: &'static str) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            Self {
+                name,
+                started_at: Instant::now(),
+            }
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = name;
+            Self {}
+        }
+    }
+
+    pub(crate) fn finish<T>(
+        self,
+        ret: &Result<T, ClientSendError>,
+        bytes: impl FnOnce(&T) -> Option<usize>,
+    ) {
+        match ret {
+            Ok(x) => self.ok(bytes(x)),
+            Err(err) => self.err(&err.kind()),
+        }
+    }
+
+    /// Only the variant of the error is logged, as `finish` does with the kind.
+    pub(crate) fn finish_parse<T, E: fmt::Debug>(self, ret: &Result<T, E>, bytes: usize) {
+        match ret {
+            Ok(_) => self.ok(Some(bytes)),
+            Err(err) => self.err(&VariantName(err)),
+        }
+    }
+
+    fn ok(self, bytes: Option<usize>) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            step = self.name,
+            bytes,
+            duration_us = duration_us(self.started_at.elapsed()),
+            "done"
+        );
+        #[cfg(not(feature = "tracing"))]
+        let _ = (self, bytes);
+    }
+
+    fn err(self, err: &dyn fmt::Debug) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            step = self.name,
+            error = ?err,
+            duration_us = duration_us(self.started_at.elapsed()),
+            "failed"
+        );
+        #[cfg(not(feature = "tracing"))]
+        let _ = (self, err);
+    }
+}
+
+//
+// Formats only the variant name, e.g. `ValueParseFailed`, the fields may carry
+// the values of the response, e.g. of `show env`, or server names.
+struct VariantName<'a>(&'a dyn fmt::Debug);
+
+impl fmt::Debug for VariantName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = format!("{:?}", self.0);
+        let len = s
+            .find(|x: char| !(x.is_alphanumeric() || x == '_'))
+            .unwrap_or(s.len());
+        f.write_str(&s[..len])
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn duration_us(duration: Duration) -> u64 {
+    duration.as_micros().try_into().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum ParseError {
+        ValueInvalid { name: Box<str>, value: Box<str> },
+        LineInvalid(Box<str>),
+        Empty,
+    }
+
+    #[test]
+    fn test_variant_name() {
+        let err = ParseError::ValueInvalid {
+            name: "SECRET".into(),
+            value: "s3cret".into(),
+        };
+        assert_eq!(format!("{:?}", VariantName(&err)), "ValueInvalid");
+        let err = ParseError::LineInvalid("srv1".into());
+        assert_eq!(format!("{:?}", VariantName(&err)), "LineInvalid");
+        assert_eq!(format!("{:?}", VariantName(&ParseError::Empty)), "Empty");
+    }
+}
@@ -0,0 +1,207 @@
+#[cfg(feature = "tokio")]
+use core::task::{Context, Poll};
+use core::{fmt, time::Duration};
+#[cfg(feature = "_async")]
+use core::{future::Future, pin::Pin};
+use std::{
+    io::{Error as IoError, ErrorKind as IoErrorKind, Read, Write},
+    net::{SocketAddr, TcpStream},
+    os::unix::net::UnixStream,
+    path::Path,
+};
+
+#[cfg(feature = "_async")]
+use futures_io::{AsyncRead, AsyncWrite};
+
+#[cfg(feature = "_async")]
+use crate::client::AsyncRuntime;
+
+//
+/// Opens the connections to the CLI, a new one for every send or session.
+///
+/// Implement it to reach the CLI over an in-memory duplex, a socket inherited from systemd,
+/// an SSH tunnel or a pre-connected fd. A transport only has to support the blocking or the
+/// async side it is used with, the other one fails with `ErrorKind::Unsupported`.
+pub trait Transport: fmt::Debug + Send + Sync + 'static {
+    /// The timeout is the remaining connect timeout, if any.
+    fn connect(&self, timeout: Option<Duration>) -> Result<BlockingStream, IoError> {
+        let _ = timeout;
+        Err(IoError::new(
+            IoErrorKind::Unsupported,
+            "blocking connect not supported",
+        ))
+    }
+
+    /// The connect timeout is applied by the caller.
+    #[cfg(feature = "_async")]
+    fn connect_async(&self, runtime: AsyncRuntime) -> ConnectFuture<'_> {
+        let _ = runtime;
+        Box::pin(async {
+            Err(IoError::new(
+                IoErrorKind::Unsupported,
+                "async connect not supported",
+            ))
+        })
+    }
+}
+
+//
+/// Blocking stream, the timeouts are optional.
+pub trait BlockingReadWrite: Read + Write + fmt::Debug + Send {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), IoError> {
+        let _ = timeout;
+        Ok(())
+    }
+
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> Result<(), IoError> {
+        let _ = timeout;
+        Ok(())
+    }
+}
+
+impl BlockingReadWrite for TcpStream {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), IoError> {
+        TcpStream::set_read_timeout(self, timeout)
+    }
+
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> Result<(), IoError> {
+        TcpStream::set_write_timeout(self, timeout)
+    }
+}
+
+impl BlockingReadWrite for UnixStream {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), IoError> {
+        UnixStream::set_read_timeout(self, timeout)
+    }
+
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> Result<(), IoError> {
+        UnixStream::set_write_timeout(self, timeout)
+    }
+}
+
+pub type BlockingStream = Box<dyn BlockingReadWrite>;
+
+//
+/// Async stream, wrap the tokio ones with `TokioIo`.
+#[cfg(feature = "_async")]
+pub trait AsyncReadWrite: AsyncRead + AsyncWrite + fmt::Debug + Send + Unpin {}
+
+#[cfg(feature = "_async")]
+impl<T> AsyncReadWrite for T where T: AsyncRead + AsyncWrite + fmt::Debug + Send + Unpin {}
+
+#[cfg(feature = "_async")]
+pub type AsyncStream = Box<dyn AsyncReadWrite>;
+
+#[cfg(feature = "_async")]
+pub type ConnectFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<AsyncStream, IoError>> + Send + 'a>>;
+
+//
+#[derive(Debug, Clone)]
+pub struct TcpTransport(pub SocketAddr);
+
+impl Transport for TcpTransport {
+    fn connect(&self, timeout: Option<Duration>) -> Result<BlockingStream, IoError> {
+        Ok(Box::new(match timeout {
+            Some(timeout) => TcpStream::connect_timeout(&self.0, timeout)?,
+            None => TcpStream::connect(self.0)?,
+        }))
+    }
+
+    #[cfg(feature = "_async")]
+    fn connect_async(&self, runtime: AsyncRuntime) -> ConnectFuture<'_> {
+        Box::pin(runtime.connect_tcp(self.0))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnixTransport(pub Box<Path>);
+
+impl Transport for UnixTransport {
+    fn connect(&self, _timeout: Option<Duration>) -> Result<BlockingStream, IoError> {
+        Ok(Box::new(UnixStream::connect(&self.0)?))
+    }
+
+    #[cfg(feature = "_async")]
+    fn connect_async(&self, runtime: AsyncRuntime) -> ConnectFuture<'_> {
+        Box::pin(runtime.connect_unix(&self.0))
+    }
+}
+
+/// Linux abstract namespace unix socket, the name without the leading NUL.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[derive(Debug, Clone)]
+pub struct AbstractUnixTransport(pub Box<[u8]>);
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl AbstractUnixTransport {
+    fn connect_std(&self) -> Result<UnixStream, IoError> {
+        #[cfg(target_os = "android")]
+        use std::os::android::net::SocketAddrExt as _;
+        #[cfg(target_os = "linux")]
+        use std::os::linux::net::SocketAddrExt as _;
+
+        let addr = std::os::unix::net::SocketAddr::from_abstract_name(&self.0)?;
+        UnixStream::connect_addr(&addr)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl Transport for AbstractUnixTransport {
+    fn connect(&self, _timeout: Option<Duration>) -> Result<BlockingStream, IoError> {
+        Ok(Box::new(self.connect_std()?))
+    }
+
+    // Connecting a unix socket does not wait for the accept.
+    #[cfg(feature = "_async")]
+    fn connect_async(&self, runtime: AsyncRuntime) -> ConnectFuture<'_> {
+        Box::pin(async move { runtime.unix_from_std(self.connect_std()?) })
+    }
+}
+
+//
+/// tokio streams to futures-io.
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct TokioIo<T>(pub T);
+
+#[cfg(feature = "tokio")]
+impl<T> AsyncRead for TokioIo<T>
+where
+    T: tokio::io::AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<usize, IoError>> {
+        let mut read_buf = tokio::io::ReadBuf::new(buf);
+        match Pin::new(&mut self.0).poll_read(cx, &mut read_buf) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(read_buf.filled().len())),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<T> AsyncWrite for TokioIo<T>
+where
+    T: tokio::io::AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, IoError>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), IoError>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), IoError>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
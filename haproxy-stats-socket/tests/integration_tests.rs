@@ -3,10 +3,48 @@
 mod integration_tests {
     mod helpers;
 
+    #[cfg(test)]
+    mod access;
+    #[cfg(test)]
+    mod address;
+    #[cfg(test)]
+    mod async_io;
+    #[cfg(test)]
+    mod blocking;
+    #[cfg(test)]
+    mod cli_error;
+    #[cfg(test)]
+    mod cli_mode;
+    #[cfg(test)]
+    mod client_builder;
+    #[cfg(test)]
+    mod client_set;
+    #[cfg(test)]
+    mod discovery;
+    #[cfg(test)]
+    mod level;
+    #[cfg(test)]
+    mod master;
+    #[cfg(test)]
+    mod pool;
+    #[cfg(test)]
+    mod retry;
+    #[cfg(test)]
+    mod send_multiple;
+    #[cfg(test)]
+    mod send_streaming;
+    #[cfg(test)]
+    mod session;
     #[cfg(test)]
     mod show_env;
     #[cfg(test)]
     mod show_info;
     #[cfg(test)]
     mod show_stat;
+    #[cfg(test)]
+    mod tls;
+    #[cfg(test)]
+    mod tracing;
+    #[cfg(test)]
+    mod transport;
 }
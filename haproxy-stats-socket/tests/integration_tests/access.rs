@@ -0,0 +1,69 @@
+use std::error;
+
+use haproxy_stats_socket::{
+    access::AdminClientError,
+    client::{Client, ClientSendError},
+    haproxy_stats::{command::ServerAdminState, CliLevel, Command, Info, ReadOnlyCommand},
+};
+
+use super::helpers::{get_tcp_addr, init_logger};
+
+#[tokio::test]
+async fn access() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let client = Client::with_tcp(get_tcp_addr()?);
+
+    let read_only_client = client.read_only();
+    let _ = read_only_client.show_info().await?;
+    let command = ReadOnlyCommand::try_from(Command::show_info()).map_err(|x| x.to_string())?;
+    let _ = Info::from_kv_bytes(read_only_client.send_async(&command).await?)?;
+    let _ = read_only_client.show_env().await?;
+
+    // Lowered, e.g. the admin commands fail even if the socket is at the admin level.
+    assert_eq!(read_only_client.show_cli_level().await?, CliLevel::User);
+    assert_eq!(read_only_client.show_cli_level_blocking()?, CliLevel::User);
+
+    //
+    let admin_client = client.admin();
+    admin_client
+        .set_server_state("bk", "srv1", ServerAdminState::Drain)
+        .await?;
+
+    let admin_client = client.with_level(CliLevel::Operator).admin();
+    match admin_client
+        .set_server_state("bk", "srv1", ServerAdminState::Ready)
+        .await
+    {
+        Err(AdminClientError::ClientSendError(ClientSendError::LevelInsufficient { .. })) => {}
+        x => panic!("{:?}", x),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn access_with_server_lifecycle() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let admin_client = Client::with_tcp(get_tcp_addr()?).admin();
+
+    admin_client
+        .add_server("http-backend", "http-backend-srv-added", "127.0.0.1:8001")
+        .await?;
+    admin_client
+        .set_server_state(
+            "http-backend",
+            "http-backend-srv-added",
+            ServerAdminState::Maint,
+        )
+        .await?;
+    admin_client
+        .shutdown_sessions_server("http-backend", "http-backend-srv-added")
+        .await?;
+    admin_client.del_server_blocking("http-backend", "http-backend-srv-added")?;
+
+    Ok(())
+}
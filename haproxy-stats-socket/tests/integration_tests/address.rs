@@ -0,0 +1,156 @@
+use std::{
+    error,
+    io::{BufRead as _, BufReader, Write as _},
+    os::{
+        linux::net::SocketAddrExt as _,
+        unix::net::{SocketAddr as UnixSocketAddr, UnixListener},
+    },
+    path::Path,
+    thread,
+};
+
+use haproxy_stats_socket::{address::HaproxyAddressParseError, client::Client, HaproxyAddress};
+
+use super::helpers::{get_tcp_addr, init_logger};
+
+#[test]
+fn haproxy_address_parse() {
+    for (s, addr) in [
+        (
+            "ipv4@127.0.0.1:9999",
+            HaproxyAddress::Tcp(([127, 0, 0, 1], 9999).into()),
+        ),
+        (
+            "ipv4@:9999",
+            HaproxyAddress::Tcp(([127, 0, 0, 1], 9999).into()),
+        ),
+        (
+            "ipv6@[::1]:9999",
+            HaproxyAddress::Tcp("[::1]:9999".parse().unwrap()),
+        ),
+        (
+            "ipv6@::1:9999",
+            HaproxyAddress::Tcp("[::1]:9999".parse().unwrap()),
+        ),
+        (
+            "10.0.0.1:9999",
+            HaproxyAddress::Tcp(([10, 0, 0, 1], 9999).into()),
+        ),
+        (
+            "ipv4@localhost:9999",
+            HaproxyAddress::Tcp(([127, 0, 0, 1], 9999).into()),
+        ),
+        (
+            "localhost:9999",
+            HaproxyAddress::Tcp(([127, 0, 0, 1], 9999).into()),
+        ),
+        (
+            "unix@/run/haproxy.sock",
+            HaproxyAddress::Unix(Path::new("/run/haproxy.sock").into()),
+        ),
+        (
+            "/run/haproxy.sock",
+            HaproxyAddress::Unix(Path::new("/run/haproxy.sock").into()),
+        ),
+        (
+            "abns@haproxy",
+            HaproxyAddress::Abstract {
+                name: "haproxy".into(),
+                padded: true,
+            },
+        ),
+        (
+            "abnsz@haproxy",
+            HaproxyAddress::Abstract {
+                name: "haproxy".into(),
+                padded: false,
+            },
+        ),
+    ] {
+        assert_eq!(s.parse::<HaproxyAddress>().unwrap(), addr, "{}", s);
+    }
+
+    assert_eq!(
+        "ipv6@::1:9999"
+            .parse::<HaproxyAddress>()
+            .unwrap()
+            .to_string(),
+        "ipv6@[::1]:9999"
+    );
+    assert_eq!(
+        "abns@haproxy"
+            .parse::<HaproxyAddress>()
+            .unwrap()
+            .to_string(),
+        "abns@haproxy"
+    );
+
+    match "fd@3".parse::<HaproxyAddress>() {
+        Err(HaproxyAddressParseError::PrefixUnsupported(prefix)) => {
+            assert_eq!(prefix.as_ref(), "fd")
+        }
+        x => panic!("{:?}", x),
+    }
+    match "ipv4@localhost".parse::<HaproxyAddress>() {
+        Err(HaproxyAddressParseError::AddrInvalid(_)) => {}
+        x => panic!("{:?}", x),
+    }
+    match "ipv4@local_host:9999".parse::<HaproxyAddress>() {
+        Err(HaproxyAddressParseError::AddrInvalid(_)) => {}
+        x => panic!("{:?}", x),
+    }
+    match "ipv4@haproxy.invalid:9999".parse::<HaproxyAddress>() {
+        Err(HaproxyAddressParseError::HostUnresolved(host)) => {
+            assert_eq!(host.as_ref(), "haproxy.invalid")
+        }
+        x => panic!("{:?}", x),
+    }
+    match format!("abns@{}", "a".repeat(108)).parse::<HaproxyAddress>() {
+        Err(HaproxyAddressParseError::NameTooLong) => {}
+        x => panic!("{:?}", x),
+    }
+}
+
+// Answers `show info` on the abstract address, as bound by HAProxy.
+fn serve_abstract(name: &[u8]) -> Result<(), Box<dyn error::Error>> {
+    let listener = UnixListener::bind_addr(&UnixSocketAddr::from_abstract_name(name)?)?;
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut line = String::new();
+            if BufReader::new(&stream).read_line(&mut line).is_err() {
+                continue;
+            }
+            let _ = (&stream).write_all(include_bytes!(
+                "../../../haproxy-stats/tests/files/2_5_5_show_info.txt"
+            ));
+        }
+    });
+    Ok(())
+}
+
+#[tokio::test]
+async fn from_haproxy_address() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let client = Client::from_haproxy_address(&format!("ipv4@{}", get_tcp_addr()?))?;
+    let info = client.show_info().await?;
+    assert!(info.pid > 0);
+
+    //
+    let name = format!("haproxy-stats-socket-{}", std::process::id());
+    let mut padded = name.clone().into_bytes();
+    padded.resize(107, 0);
+    serve_abstract(&padded)?;
+    serve_abstract(format!("{}-z", name).as_bytes())?;
+
+    let client = Client::from_haproxy_address(&format!("abns@{}", name))?;
+    assert_eq!(client.show_info().await?.pid, 8);
+    assert_eq!(client.show_info_blocking()?.pid, 8);
+
+    let client = Client::from_haproxy_address(&format!("abnsz@{}-z", name))?;
+    assert_eq!(client.show_info().await?.pid, 8);
+    assert_eq!(client.show_info_blocking()?.pid, 8);
+
+    Ok(())
+}
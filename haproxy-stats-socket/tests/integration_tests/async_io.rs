@@ -0,0 +1,37 @@
+use std::error;
+
+use haproxy_stats_socket::{
+    client::{AsyncRuntime, Client},
+    haproxy_stats::Command,
+};
+
+use super::helpers::{get_tcp_addr, init_logger};
+
+#[test]
+fn async_io() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    async_io::block_on(async {
+        let client = Client::builder_with_tcp(get_tcp_addr()?)
+            .async_runtime(AsyncRuntime::AsyncIo)
+            .build();
+
+        let info = client.show_info().await?;
+        assert!(info.pid > 0);
+
+        let vars = client.show_env().await?;
+        assert!(!vars.is_empty());
+
+        let mut session = client.session();
+        for _ in 0..3 {
+            let _ = session.send(&Command::show_info()).await?;
+        }
+        assert!(session.is_connected());
+
+        let pool = client.pool(2);
+        let _ = pool.show_info().await?;
+        assert_eq!(pool.idle_count(), 1);
+
+        Ok(())
+    })
+}
@@ -0,0 +1,33 @@
+use std::error;
+
+use haproxy_stats_socket::{client::Client, haproxy_stats::CliLevel};
+
+use super::helpers::{get_tcp_addr, init_logger};
+
+#[test]
+fn blocking() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let client = Client::with_tcp(get_tcp_addr()?);
+
+    let info = client.show_info_blocking()?;
+    assert!(info.pid > 0);
+
+    let statistics = client.show_stat_blocking()?;
+    assert!(!statistics.is_empty());
+
+    let vars = client.show_env_blocking()?;
+    assert!(!vars.is_empty());
+    assert!(client
+        .show_env_with_name_blocking("FOO_NOT_EXISTS")?
+        .is_none());
+
+    let client = client.detect_level_blocking()?;
+    assert_eq!(client.level(), Some(CliLevel::Admin));
+
+    let read_only_client = client.read_only();
+    let _ = read_only_client.show_info_blocking()?;
+
+    Ok(())
+}
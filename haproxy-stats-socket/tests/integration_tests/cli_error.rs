@@ -0,0 +1,26 @@
+use std::error;
+
+use haproxy_stats_socket::{
+    client::{Client, ClientShowProcError},
+    haproxy_stats::CliErrorKind,
+};
+
+use super::helpers::{get_tcp_addr, init_logger};
+
+#[tokio::test]
+async fn cli_error() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let client = Client::with_tcp(get_tcp_addr()?);
+
+    // Only on the master CLI.
+    match client.show_proc().await {
+        Err(ClientShowProcError::CliError(err)) => {
+            assert_eq!(err.kind, CliErrorKind::UnknownCommand);
+        }
+        x => panic!("{:?}", x),
+    }
+
+    Ok(())
+}
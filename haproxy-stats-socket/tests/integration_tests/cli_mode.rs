@@ -0,0 +1,187 @@
+use core::time::Duration;
+use std::{
+    error,
+    io::{BufRead as _, BufReader, Write as _},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use haproxy_stats_socket::{
+    client::{Client, ClientSendError, ClientWaitError},
+    haproxy_stats::{CliErrorKind, CliLevel, CliMode, Command, WaitStatus},
+};
+
+use super::helpers::init_logger;
+
+type Lines = Arc<Mutex<Vec<String>>>;
+
+// CLI of HAProxy 2.7+, keeps the received lines.
+fn start_cli() -> Result<(SocketAddr, Lines), Box<dyn error::Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    let lines = Arc::new(Mutex::new(vec![]));
+
+    let lines_ = lines.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let lines = lines_.clone();
+            thread::spawn(move || handle(stream, lines));
+        }
+    });
+
+    Ok((addr, lines))
+}
+
+fn handle(mut stream: TcpStream, lines: Lines) {
+    let mut reader = BufReader::new(stream.try_clone().expect(""));
+    let mut interactive = false;
+    let mut line = String::new();
+    while reader.read_line(&mut line).unwrap_or(0) > 0 {
+        let command = line.trim().to_owned();
+        line.clear();
+        lines.lock().expect("").push(command.clone());
+
+        let response = match command.as_str() {
+            "prompt" => {
+                interactive = !interactive;
+                ""
+            }
+            "quit" => break,
+            x if x.starts_with("wait ") && x.ends_with("bk/missing") => "No such server.\n",
+            x if x.starts_with("wait ") && x.ends_with("bk/busy") => "Wait delay expired.\n",
+            x if x.starts_with("wait ") => "Done.\n",
+            _ => "",
+        };
+        let prompt = if interactive { "\n> " } else { "\n" };
+        if stream
+            .write_all(format!("{}{}", response, prompt).as_bytes())
+            .is_err()
+            || !interactive
+        {
+            break;
+        }
+    }
+}
+
+fn take_lines(lines: &Mutex<Vec<String>>) -> Vec<String> {
+    core::mem::take(&mut *lines.lock().expect(""))
+}
+
+#[tokio::test]
+async fn cli_mode() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    let (addr, lines) = start_cli()?;
+
+    //
+    let client = Client::with_tcp(addr);
+    client
+        .send_async(&Command::new("debug dev log foo")?)
+        .await?;
+    assert_eq!(
+        take_lines(&lines),
+        vec!["prompt", "expert-mode on", "debug dev log foo", "quit"]
+    );
+
+    client.send_async(&Command::show_stat()).await?;
+    assert_eq!(take_lines(&lines), vec!["show stat"]);
+
+    //
+    let client = Client::builder_with_tcp(addr)
+        .mode(CliMode::Experimental)
+        .build();
+    client.send(&Command::new("debug dev log foo")?)?;
+    assert_eq!(
+        take_lines(&lines),
+        vec![
+            "prompt",
+            "expert-mode on",
+            "experimental-mode on",
+            "debug dev log foo",
+            "quit"
+        ]
+    );
+
+    //
+    let client = Client::builder_with_tcp(addr)
+        .level(CliLevel::Operator)
+        .build();
+    match client
+        .send_async(&Command::show_stat().require_mode(CliMode::Expert))
+        .await
+    {
+        Err(ClientSendError::LevelInsufficient { required, .. }) => {
+            assert_eq!(required, CliLevel::Admin)
+        }
+        x => panic!("{:?}", x),
+    }
+    assert!(take_lines(&lines).is_empty());
+
+    //
+    let mut session = Client::with_tcp(addr).session();
+    session.set_mode(CliMode::Experimental, true).await?;
+    session.send(&Command::show_info()).await?;
+    session.send(&Command::new("debug dev log foo")?).await?;
+    assert_eq!(
+        session.modes(),
+        &[CliMode::Experimental, CliMode::Expert][..]
+    );
+    session.set_mode(CliMode::Experimental, false).await?;
+    assert_eq!(
+        take_lines(&lines),
+        vec![
+            "prompt",
+            "experimental-mode on",
+            "show info",
+            "expert-mode on",
+            "debug dev log foo",
+            "experimental-mode off"
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn wait() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    let (addr, lines) = start_cli()?;
+    let client = Client::with_tcp(addr);
+
+    assert_eq!(
+        client.wait(Duration::from_millis(100)).await?,
+        WaitStatus::Done
+    );
+    assert!(client
+        .wait_srv_removable_blocking(Duration::from_secs(1), "bk", "srv1")?
+        .is_done());
+    assert_eq!(
+        client
+            .wait_srv_removable(Duration::from_secs(1), "bk", "busy")
+            .await?,
+        WaitStatus::Expired
+    );
+    match client
+        .wait_srv_removable(Duration::from_secs(1), "bk", "missing")
+        .await
+    {
+        Err(ClientWaitError::CliError(err)) => assert_eq!(err.kind, CliErrorKind::NotFound),
+        x => panic!("{:?}", x),
+    }
+    assert_eq!(
+        take_lines(&lines),
+        vec![
+            "wait 100",
+            "wait 1000 srv-removable bk/srv1",
+            "wait 1000 srv-removable bk/busy",
+            "wait 1000 srv-removable bk/missing"
+        ]
+    );
+
+    let mut session = client.session();
+    assert!(session.wait(Duration::from_millis(10)).await?.is_done());
+
+    Ok(())
+}
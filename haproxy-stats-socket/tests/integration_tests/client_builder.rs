@@ -0,0 +1,76 @@
+use core::time::Duration;
+use std::error;
+
+use haproxy_stats_socket::{
+    client::{ClientBuilder, ClientSendError, ClientShowStatError},
+    haproxy_stats::Command,
+};
+use tokio::net::TcpListener;
+
+use super::helpers::{get_tcp_addr, init_logger};
+
+#[tokio::test]
+async fn max_response_size() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let client = ClientBuilder::with_tcp(get_tcp_addr()?)
+        .max_response_size(100)
+        .build();
+
+    match client.show_stat().await {
+        Err(ClientShowStatError::ClientSendError(ClientSendError::ResponseTooLarge)) => {}
+        x => panic!("{:?}", x),
+    }
+
+    match client.send(&Command::show_stat()) {
+        Err(ClientSendError::ResponseTooLarge) => {}
+        x => panic!("{:?}", x),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn timeout() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    // Accepts but never responds, like a wedged CLI.
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        let mut streams = vec![];
+        while let Ok((stream, _)) = listener.accept().await {
+            streams.push(stream);
+        }
+    });
+
+    //
+    let client = ClientBuilder::with_tcp(addr)
+        .read_timeout(Duration::from_millis(100))
+        .build();
+
+    match client.send_async(&Command::show_stat()).await {
+        Err(ClientSendError::ReadTimeout) => {}
+        x => panic!("{:?}", x),
+    }
+
+    //
+    let client = ClientBuilder::with_tcp(addr)
+        .read_timeout(Duration::from_secs(5))
+        .timeout(Duration::from_millis(100))
+        .build();
+
+    match client.send_async(&Command::show_stat()).await {
+        Err(ClientSendError::Timeout) => {}
+        x => panic!("{:?}", x),
+    }
+
+    let client = client.clone();
+    match tokio::task::spawn_blocking(move || client.send(&Command::show_stat())).await? {
+        Err(ClientSendError::Timeout) => {}
+        x => panic!("{:?}", x),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use core::time::Duration;
+use std::{error, net::TcpListener, time::Instant};
+
+use haproxy_stats_socket::{
+    client::{Client, ClientSendErrorKind, ClientShowInfoError, RetryPolicy},
+    client_set::ClientSetNodeError,
+    haproxy_stats::Command,
+    ClientSet,
+};
+
+use super::helpers::{get_tcp_addr, init_logger};
+
+// Accepts, then never answers.
+fn slow_node() -> Result<(TcpListener, Client), Box<dyn error::Error>> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let client = Client::with_tcp(listener.local_addr()?);
+    Ok((listener, client))
+}
+
+#[tokio::test]
+async fn client_set() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    let (_listener, slow_client) = slow_node()?;
+    let set = ClientSet::new()
+        .node("edge-1", Client::with_tcp(get_tcp_addr()?))
+        .node("edge-2", Client::with_tcp(get_tcp_addr()?))
+        .node("edge-down", Client::with_tcp(([127, 0, 0, 1], 1)))
+        .node("edge-slow", slow_client)
+        .concurrency(2)
+        .timeout(Duration::from_millis(300));
+    assert_eq!(set.len(), 4);
+
+    //
+    let results = set.show_info().await;
+    assert_eq!(results.len(), 4);
+    assert!(results["edge-1"].as_ref().unwrap().pid > 0);
+    assert!(results["edge-2"].as_ref().unwrap().pid > 0);
+    match &results["edge-down"] {
+        Err(ClientSetNodeError::Failed(ClientShowInfoError::ClientSendError(err))) => {
+            assert_eq!(err.kind(), ClientSendErrorKind::ConnectFailed)
+        }
+        x => panic!("{:?}", x),
+    }
+    match &results["edge-slow"] {
+        Err(ClientSetNodeError::Timeout) => {}
+        x => panic!("{:?}", x),
+    }
+
+    let results = set.show_stat().await;
+    assert!(!results["edge-1"].as_ref().unwrap().is_empty());
+
+    let results = set
+        .run(|client| async move { client.send_async(&Command::show_env()).await })
+        .await;
+    assert!(!results["edge-2"].as_ref().unwrap().is_empty());
+
+    //
+    let results = set.show_info_blocking();
+    assert_eq!(results.len(), 4);
+    assert!(results["edge-1"].as_ref().unwrap().pid > 0);
+    assert!(results["edge-2"].as_ref().unwrap().pid > 0);
+    match &results["edge-slow"] {
+        Err(ClientSetNodeError::Timeout) => {}
+        x => panic!("{:?}", x),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn client_set_with_retry() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    // Every read times out and is retried, the timeout of the node covers all of them.
+    let (listener, _) = slow_node()?;
+    let slow_client = Client::builder_with_tcp(listener.local_addr()?)
+        .read_timeout(Duration::from_millis(200))
+        .retry_policy(
+            RetryPolicy::new(5)
+                .initial_backoff(Duration::from_millis(0))
+                .retryable([ClientSendErrorKind::ReadTimeout]),
+        )
+        .build();
+    let set = ClientSet::new()
+        .node("edge-slow", slow_client)
+        .timeout(Duration::from_millis(300));
+
+    let now = Instant::now();
+    let results = set.show_info().await;
+    assert!(matches!(
+        results["edge-slow"],
+        Err(ClientSetNodeError::Timeout)
+    ));
+    assert!(now.elapsed() < Duration::from_millis(600));
+
+    let now = Instant::now();
+    let results = set.show_info_blocking();
+    assert!(matches!(
+        results["edge-slow"],
+        Err(ClientSetNodeError::Timeout)
+    ));
+    assert!(now.elapsed() < Duration::from_millis(600));
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use std::error;
+
+use haproxy_stats_socket::{haproxy_stats::CliLevel, StatsSocketConfig};
+
+use super::helpers::{get_tcp_addr, init_logger};
+
+#[tokio::test]
+async fn stats_socket_config_client() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    let port = get_tcp_addr()?.port();
+    let configs = StatsSocketConfig::from_config_bytes_with_env(
+        "global\n    stats socket ipv4@*:${PORT} level user\n",
+        |_| Some(port.to_string()),
+    );
+
+    let client = configs[0].as_ref().map_err(|x| x.to_string())?.client();
+    assert_eq!(client.level(), Some(CliLevel::User));
+
+    let info = client.show_info().await?;
+    assert!(info.pid > 0);
+
+    Ok(())
+}
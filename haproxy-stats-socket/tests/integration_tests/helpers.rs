@@ -15,6 +15,20 @@ pub(super) fn get_tcp_addr() -> Result<SocketAddr, Box<dyn error::Error>> {
     Ok(SocketAddr::new(ip_addr, port))
 }
 
+/// The master CLI is optional, e.g. `haproxy -W -S :9256`.
+pub(super) fn get_master_tcp_addr() -> Result<Option<SocketAddr>, Box<dyn error::Error>> {
+    let port = match env::var("HAPROXY_MASTER_SOCKET_TCP_PORT") {
+        Ok(port) => port,
+        Err(_) => return Ok(None),
+    };
+    debug!("HAPROXY_MASTER_SOCKET_TCP_PORT {}", port);
+
+    let ip_addr = "127.0.0.1".parse::<IpAddr>()?;
+    let port = port.parse::<u16>()?;
+
+    Ok(Some(SocketAddr::new(ip_addr, port)))
+}
+
 pub(super) fn get_unix_path() -> Result<String, Box<dyn error::Error>> {
     let path = env::var("HAPROXY_STATS_SOCKET_UNIX_PATH")?;
     debug!("HAPROXY_STATS_SOCKET_UNIX_PATH {}", path);
@@ -0,0 +1,45 @@
+use std::error;
+
+use haproxy_stats_socket::{
+    client::{Client, ClientSendError, ClientShowEnvError},
+    haproxy_stats::CliLevel,
+};
+
+use super::helpers::{get_tcp_addr, init_logger};
+
+#[tokio::test]
+async fn level() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let client = Client::with_tcp(get_tcp_addr()?);
+
+    let client = client.detect_level().await?;
+    assert_eq!(client.level(), Some(CliLevel::Admin));
+
+    //
+    let client = Client::builder_with_tcp(get_tcp_addr()?)
+        .level(CliLevel::User)
+        .build();
+
+    assert_eq!(client.show_cli_level().await?, CliLevel::User);
+    let _ = client.show_info().await?;
+
+    match client.show_env().await {
+        Err(ClientShowEnvError::ClientSendError(ClientSendError::LevelInsufficient {
+            required,
+            level,
+            ..
+        })) => {
+            assert_eq!(required, CliLevel::Operator);
+            assert_eq!(level, CliLevel::User);
+        }
+        x => panic!("{:?}", x),
+    }
+
+    //
+    let mut session = client.session();
+    assert_eq!(session.show_cli_level().await?, CliLevel::User);
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use core::time::Duration;
+use std::error;
+
+use haproxy_stats_socket::{MasterClient, ProcessTarget, ReloadOptions, ReloadOutcome};
+
+use super::helpers::{get_master_tcp_addr, init_logger};
+
+#[tokio::test]
+async fn master() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let addr = match get_master_tcp_addr()? {
+        Some(addr) => addr,
+        None => return Ok(()),
+    };
+    let master = MasterClient::with_tcp(addr);
+
+    let processes = master.show_proc().await?;
+    assert!(processes.master.is_some());
+    assert!(!processes.workers.is_empty());
+
+    //
+    let info = master.route(ProcessTarget::Relative(1)).show_info().await?;
+    assert!(processes.worker_pids().contains(&(info.pid as u32)));
+
+    let statistics = master
+        .route(ProcessTarget::Pid(processes.workers[0].pid))
+        .show_stat()
+        .await?;
+    assert!(!statistics.is_empty());
+
+    //
+    let ret = master
+        .for_each_worker(|x| async move { x.show_info().await })
+        .await?;
+    assert_eq!(ret.len(), processes.workers.len());
+    for (process, info) in ret {
+        assert_eq!(process.pid as usize, info?.pid);
+    }
+
+    let workers = master.workers_blocking()?;
+    assert_eq!(workers.len(), processes.workers.len());
+
+    //
+    let options = ReloadOptions::new()
+        .poll_interval(Duration::from_millis(100))
+        .drain_timeout(Duration::from_secs(5));
+    match master.reload(&options).await? {
+        ReloadOutcome::Success {
+            workers,
+            leaving_workers,
+        } => {
+            assert!(workers
+                .iter()
+                .all(|x| !processes.worker_pids().contains(&x.pid)));
+            assert!(leaving_workers.is_empty());
+        }
+        x => panic!("{:?}", x),
+    }
+
+    Ok(())
+}
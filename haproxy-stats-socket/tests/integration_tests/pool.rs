@@ -0,0 +1,85 @@
+use core::time::Duration;
+use std::error;
+
+use haproxy_stats_socket::{client::Client, haproxy_stats::Command, Pool};
+
+use super::{
+    helpers::{get_tcp_addr, init_logger},
+    transport::{MemoryTransport, SLOW_DELAY},
+};
+
+#[tokio::test]
+async fn pool() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let client = Client::with_tcp(get_tcp_addr()?);
+
+    let pool = Pool::builder(client)
+        .max_size(2)
+        .health_check_interval(Some(Duration::from_millis(0)))
+        .build();
+    assert_eq!(pool.idle_count(), 0);
+
+    let handles = (0..6)
+        .map(|_| {
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                let info = pool.show_info().await?;
+                let statistics = pool.show_stat().await?;
+                let _ = pool.show_env().await?;
+                Result::<_, Box<dyn error::Error + Send + Sync>>::Ok((info, statistics))
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        let (_, statistics) = handle.await?.map_err(|err| err.to_string())?;
+        assert!(!statistics.is_empty());
+    }
+    assert!(pool.idle_count() <= 2);
+    assert!(pool.idle_count() >= 1);
+
+    //
+    {
+        let mut session = pool.get().await;
+        assert!(session.is_connected());
+        let _ = session.show_info().await?;
+    }
+
+    //
+    let pool = Pool::builder(pool.client().to_owned())
+        .idle_timeout(Duration::from_millis(0))
+        .build();
+    let _ = pool.show_info().await?;
+    pool.evict_idle();
+    assert_eq!(pool.idle_count(), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn pool_with_cancelled_send() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let pool = Client::with_transport(MemoryTransport).pool(1);
+
+    let info = pool.show_info().await?;
+    assert_eq!(info.pid, 8);
+    assert_eq!(pool.idle_count(), 1);
+
+    // Cancelled after the first part of the response, the session is not reused.
+    {
+        let mut session = pool.get().await;
+        let ret =
+            tokio::time::timeout(SLOW_DELAY / 2, session.send(&Command::new("show sess")?)).await;
+        assert!(ret.is_err());
+    }
+    assert_eq!(pool.idle_count(), 0);
+
+    let info = pool.show_info().await?;
+    assert_eq!(info.pid, 8);
+    assert_eq!(pool.idle_count(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use core::time::Duration;
+use std::{
+    error,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use haproxy_stats_socket::{
+    client::{ClientBuilder, ClientSendError, RetryPolicy},
+    haproxy_stats::Command,
+};
+use tokio::{
+    io::{AsyncReadExt as _, AsyncWriteExt as _},
+    net::TcpListener,
+};
+
+use super::helpers::init_logger;
+
+#[tokio::test]
+async fn retry() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    // Resets every other connection, like a reloading master-worker.
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    let accepted = Arc::new(AtomicUsize::new(0));
+    let accepted_cloned = accepted.clone();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buf = vec![0; 1024];
+            let _ = stream.read(&mut buf).await;
+
+            if accepted_cloned.fetch_add(1, Ordering::SeqCst) & 1 == 0 {
+                #[allow(deprecated)]
+                let _ = stream.set_linger(Some(Duration::ZERO));
+            } else {
+                let _ = stream.write_all(b"\n").await;
+            }
+        }
+    });
+
+    //
+    let client = ClientBuilder::with_tcp(addr)
+        .retry_policy(RetryPolicy::new(2).initial_backoff(Duration::from_millis(10)))
+        .build();
+
+    client.send_async(&Command::show_stat()).await?;
+    assert_eq!(accepted.load(Ordering::SeqCst), 2);
+
+    //
+    match client
+        .send_async(&Command::new("set weight bk/srv1 50%")?)
+        .await
+    {
+        Err(ClientSendError::ReadFailed(_)) => {}
+        x => panic!("{:?}", x),
+    }
+    assert_eq!(accepted.load(Ordering::SeqCst), 3);
+
+    client
+        .send_async(&Command::new("set weight bk/srv1 50%")?.mark_idempotent())
+        .await?;
+    assert_eq!(accepted.load(Ordering::SeqCst), 4);
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::{error, time::Duration};
+
+use haproxy_stats_socket::{
+    client::Client,
+    haproxy_stats::{Command, Commands, Info, Statistics},
+};
+
+use super::helpers::{get_tcp_addr, init_logger};
+
+#[tokio::test]
+async fn send_multiple() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let client = Client::with_tcp(get_tcp_addr()?);
+
+    let commands = vec![Command::show_info(), Command::show_stat()];
+
+    let responses = client.send_multiple(Commands::new(&commands))?;
+    assert_eq!(responses.len(), 2);
+    let _ = Info::from_kv_bytes(&responses[0])?;
+    let _ = Statistics::from_csv_bytes(&responses[1])?;
+
+    let responses = client.send_multiple_async(Commands::new(&commands)).await?;
+    assert_eq!(responses.len(), 2);
+    let _ = Info::from_kv_bytes(&responses[0])?;
+    let _ = Statistics::from_csv_bytes(&responses[1])?;
+
+    // Pipelined, the responses are split on the prompts.
+    let commands = vec![
+        Command::show_cli_level(),
+        Command::set_timeout_cli(Duration::from_secs(10)),
+        Command::show_info(),
+        Command::show_cli_level(),
+    ];
+
+    for responses in [
+        client.send_multiple(Commands::new(&commands))?,
+        client.send_multiple_async(Commands::new(&commands)).await?,
+    ] {
+        assert_eq!(responses.len(), 4);
+        assert_eq!(String::from_utf8_lossy(&responses[0]).trim(), "admin");
+        assert_eq!(String::from_utf8_lossy(&responses[1]).trim(), "");
+        let _ = Info::from_kv_bytes(&responses[2])?;
+        assert_eq!(String::from_utf8_lossy(&responses[3]).trim(), "admin");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use core::time::Duration;
+use std::{
+    error,
+    io::{ErrorKind as IoErrorKind, Read as _},
+};
+
+use haproxy_stats_socket::{
+    client::{Client, ClientSendError},
+    haproxy_stats::{CliLevel, Command},
+};
+use tokio::{io::AsyncReadExt as _, net::TcpListener};
+
+use super::helpers::{get_tcp_addr, init_logger};
+
+#[tokio::test]
+async fn send_streaming() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    let client = Client::with_tcp(get_tcp_addr()?);
+    let expected = client.send_async(&Command::show_env()).await?;
+    assert!(!expected.is_empty());
+
+    //
+    let mut reader = client.send_streaming_async(&Command::show_env()).await?;
+    let mut response = vec![];
+    reader.read_to_end(&mut response).await?;
+    assert_eq!(response, expected);
+
+    let mut reader = client.send_streaming(&Command::show_env())?;
+    let mut response = vec![];
+    reader.read_to_end(&mut response)?;
+    assert_eq!(response, expected);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn send_streaming_with_level() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    let client = Client::builder_with_tcp(get_tcp_addr()?)
+        .level(CliLevel::User)
+        .build();
+    let expected = client.send_async(&Command::show_cli_level()).await?;
+    assert!(expected.starts_with(b"user"));
+
+    //
+    let mut reader = client
+        .send_streaming_async(&Command::show_cli_level())
+        .await?;
+    let mut response = vec![];
+    reader.read_to_end(&mut response).await?;
+    assert_eq!(response, expected);
+
+    let mut reader = client.send_streaming(&Command::show_cli_level())?;
+    let mut response = vec![];
+    reader.read_to_end(&mut response)?;
+    assert_eq!(response, expected);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn send_streaming_with_read_timeout() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    // Accepts, then never responds.
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        drop(stream);
+    });
+
+    let client = Client::builder_with_tcp(addr)
+        .read_timeout(Duration::from_millis(100))
+        .build();
+
+    let mut reader = client.send_streaming_async(&Command::show_env()).await?;
+    let err = reader.read_to_end(&mut vec![]).await.unwrap_err();
+    assert_eq!(err.kind(), IoErrorKind::TimedOut);
+    match err
+        .get_ref()
+        .and_then(|x| x.downcast_ref::<ClientSendError>())
+    {
+        Some(ClientSendError::ReadTimeout) => {}
+        x => panic!("{:?}", x),
+    }
+
+    Ok(())
+}
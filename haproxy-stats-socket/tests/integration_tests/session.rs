@@ -0,0 +1,65 @@
+use core::time::Duration;
+use std::error;
+
+use haproxy_stats_socket::{
+    client::Client,
+    haproxy_stats::{Command, Info, Statistics},
+};
+
+use super::{
+    helpers::{get_tcp_addr, init_logger},
+    transport::{MemoryTransport, SLOW_DELAY},
+};
+
+#[tokio::test]
+async fn session() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let client = Client::with_tcp(get_tcp_addr()?);
+
+    let mut session = client.session();
+    assert!(!session.is_connected());
+
+    session.set_timeout_cli(Duration::from_secs(60)).await?;
+
+    for _ in 0..3 {
+        let res = session.send(&Command::show_info()).await?;
+        let _ = Info::from_kv_bytes(res)?;
+
+        let res = session.send(&Command::show_stat()).await?;
+        let _ = Statistics::from_csv_bytes(res)?;
+    }
+    assert!(session.is_connected());
+
+    session.close().await?;
+    assert!(!session.is_connected());
+
+    let res = session.send(&Command::show_info()).await?;
+    let _ = Info::from_kv_bytes(res)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn session_with_cancelled_send() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let client = Client::with_transport(MemoryTransport);
+
+    let mut session = client.session();
+    let _ = Info::from_kv_bytes(session.send(&Command::show_info()).await?)?;
+    assert!(session.is_connected());
+
+    // Cancelled after the first part of the response.
+    let ret = tokio::time::timeout(SLOW_DELAY / 2, session.send(&Command::new("show sess")?)).await;
+    assert!(ret.is_err());
+    assert!(!session.is_connected());
+
+    let info = Info::from_kv_bytes(session.send(&Command::show_info()).await?)?;
+    assert_eq!(info.pid, 8);
+    assert!(session.is_connected());
+
+    Ok(())
+}
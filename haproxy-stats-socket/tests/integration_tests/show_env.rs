@@ -14,5 +14,12 @@ async fn show_env() -> Result<(), Box<dyn error::Error>> {
     let env_vars = client.show_env().await?;
     println!("env_vars {:?}", env_vars);
 
+    let cfgfiles = client.show_env_with_name("HAPROXY_CFGFILES").await?;
+    println!("cfgfiles {:?}", cfgfiles);
+    assert_eq!(cfgfiles, env_vars.get("HAPROXY_CFGFILES").cloned());
+
+    let not_found = client.show_env_with_name("HAPROXY_NOT_EXISTS").await?;
+    assert_eq!(not_found, None);
+
     Ok(())
 }
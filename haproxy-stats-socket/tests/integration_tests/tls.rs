@@ -0,0 +1,147 @@
+use std::{
+    error,
+    io::{BufRead as _, BufReader, Write as _},
+    net::{SocketAddr, TcpListener},
+    sync::Arc,
+    thread,
+};
+
+use haproxy_stats_socket::{
+    client::{Client, ClientSendErrorKind, ClientShowInfoError},
+    rustls::{
+        crypto::ring, pki_types::PrivateKeyDer, server::WebPkiClientVerifier, RootCertStore,
+        ServerConfig, ServerConnection, StreamOwned,
+    },
+    tls::TlsTransportError,
+    TlsOptions,
+};
+use rcgen::{BasicConstraints, Certificate, CertificateParams, IsCa, KeyPair};
+
+use super::helpers::init_logger;
+
+//
+struct Pki {
+    ca_cert: Certificate,
+    ca_key: KeyPair,
+}
+
+impl Pki {
+    fn new() -> Result<Self, Box<dyn error::Error>> {
+        let ca_key = KeyPair::generate()?;
+        let mut params = CertificateParams::new(Vec::<String>::new())?;
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca_cert = params.self_signed(&ca_key)?;
+        Ok(Self { ca_cert, ca_key })
+    }
+
+    fn issue(&self, name: &str) -> Result<(Certificate, KeyPair), Box<dyn error::Error>> {
+        let key = KeyPair::generate()?;
+        let cert = CertificateParams::new(vec![name.to_owned()])?.signed_by(
+            &key,
+            &self.ca_cert,
+            &self.ca_key,
+        )?;
+        Ok((cert, key))
+    }
+}
+
+// TLS terminator stand-in, answers `show info` and requires a client certificate.
+fn serve(pki: &Pki) -> Result<SocketAddr, Box<dyn error::Error>> {
+    let (cert, key) = pki.issue("localhost")?;
+
+    let provider = Arc::new(ring::default_provider());
+    let mut roots = RootCertStore::empty();
+    roots.add(pki.ca_cert.der().clone())?;
+    let verifier =
+        WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider.clone()).build()?;
+    let config = Arc::new(
+        ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_client_cert_verifier(verifier)
+            .with_single_cert(
+                vec![cert.der().clone()],
+                PrivateKeyDer::Pkcs8(key.serialize_der().into()),
+            )?,
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    thread::spawn(move || {
+        for sock in listener.incoming().flatten() {
+            let config = config.clone();
+            thread::spawn(move || {
+                let conn = ServerConnection::new(config).unwrap();
+                let mut stream = StreamOwned::new(conn, sock);
+
+                let mut line = String::new();
+                if BufReader::new(&mut stream).read_line(&mut line).is_err() {
+                    return;
+                }
+                let response: &[u8] = match line.trim() {
+                    "show info" => {
+                        include_bytes!("../../../haproxy-stats/tests/files/2_5_5_show_info.txt")
+                    }
+                    _ => b"Unknown command.\n",
+                };
+                let _ = stream.write_all(response);
+                stream.conn.send_close_notify();
+                let _ = stream.flush();
+            });
+        }
+    });
+
+    Ok(addr)
+}
+
+#[tokio::test]
+async fn tls() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    let pki = Pki::new()?;
+    let addr = serve(&pki)?;
+    let (client_cert, client_key) = pki.issue("client")?;
+
+    let options = TlsOptions::default()
+        .ca_pem(pki.ca_cert.pem())
+        .client_auth_pem(client_cert.pem(), client_key.serialize_pem())
+        .server_name("localhost");
+
+    //
+    let client = Client::with_tls(addr, &options)?;
+
+    let info = client.show_info().await?;
+    assert_eq!(info.pid, 8);
+
+    let info = client.show_info_blocking()?;
+    assert_eq!(info.pid, 8);
+
+    // Name not in the server certificate.
+    let client = Client::with_tls(addr, &options.clone().server_name("haproxy.internal"))?;
+    match client.show_info().await {
+        Err(ClientShowInfoError::ClientSendError(err)) => {
+            assert_eq!(err.kind(), ClientSendErrorKind::ConnectFailed)
+        }
+        x => panic!("{:?}", x),
+    }
+    match client.show_info_blocking() {
+        Err(ClientShowInfoError::ClientSendError(err)) => {
+            assert_eq!(err.kind(), ClientSendErrorKind::ConnectFailed)
+        }
+        x => panic!("{:?}", x),
+    }
+
+    // Without the client certificate.
+    let options = TlsOptions::default()
+        .ca_pem(pki.ca_cert.pem())
+        .server_name("localhost");
+    let client = Client::with_tls(addr, &options)?;
+    assert!(client.show_info().await.is_err());
+
+    //
+    match Client::with_tls(addr, &TlsOptions::default()) {
+        Err(TlsTransportError::CaMissing) => {}
+        x => panic!("{:?}", x),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use core::fmt;
+use std::{
+    error,
+    fmt::Write as _,
+    sync::{Arc, Mutex},
+};
+
+use haproxy_stats_socket::{client::Client, haproxy_stats::Command};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+use super::helpers::{get_tcp_addr, init_logger};
+
+// Keeps the spans and the events as lines, e.g. `event step="read" bytes=42 ...`.
+#[derive(Debug, Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+struct Line(String);
+
+impl Visit for Line {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let _ = write!(self.0, " {}={:?}", field.name(), value);
+    }
+}
+
+impl Recorder {
+    fn push(&self, line: Line) {
+        self.0.lock().expect("").push(line.0);
+    }
+
+    fn lines(&self) -> Vec<String> {
+        self.0.lock().expect("").clone()
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut line = Line(format!("span {}", span.metadata().name()));
+        span.record(&mut line);
+        self.push(line);
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, values: &Record<'_>) {
+        let mut line = Line("record".to_owned());
+        values.record(&mut line);
+        self.push(line);
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut line = Line("event".to_owned());
+        event.record(&mut line);
+        self.push(line);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn tracing() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    let client = Client::with_tcp(get_tcp_addr()?);
+
+    //
+    let recorder = Recorder::default();
+    let info = tracing::subscriber::with_default(recorder.clone(), || client.show_info_blocking())?;
+    assert!(info.pid > 0);
+
+    let lines = recorder.lines();
+    assert!(lines[0].starts_with("span haproxy_stats_socket.send"));
+    assert!(lines[0].contains("TcpTransport"));
+    assert!(lines.contains(&r#"record command="show info""#.to_owned()));
+    for step in ["connect", "write", "read", "parse"] {
+        assert!(
+            lines
+                .iter()
+                .any(|x| x.contains(&format!(r#"message=done step="{}""#, step))),
+            "{:?}",
+            lines
+        );
+    }
+    assert!(lines.iter().any(|x| x.contains("bytes=11")));
+
+    //
+    let recorder = Recorder::default();
+    let command = Command::new("set map /etc/haproxy/tokens.map user1 s3cret")?;
+    let _ = tracing::subscriber::with_default(recorder.clone(), || client.send(&command));
+
+    let lines = recorder.lines();
+    assert!(lines.contains(&r#"record command="set map <redacted>""#.to_owned()));
+    assert!(!lines.iter().any(|x| x.contains("s3cret")));
+
+    //
+    let recorder = Recorder::default();
+    let down = Client::with_tcp(([127, 0, 0, 1], 1));
+    let ret =
+        tracing::subscriber::with_default(recorder.clone(), || down.send(&Command::show_info()));
+    assert!(ret.is_err());
+    assert!(recorder
+        .lines()
+        .iter()
+        .any(|x| x.contains(r#"message=failed step="connect" error=ConnectFailed"#)));
+
+    Ok(())
+}
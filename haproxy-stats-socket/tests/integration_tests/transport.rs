@@ -0,0 +1,143 @@
+use core::time::Duration;
+use std::{
+    error,
+    io::{BufRead as _, BufReader, Error as IoError, Write as _},
+    os::unix::net::UnixStream,
+    thread,
+};
+
+use haproxy_stats_socket::{
+    client::{AsyncRuntime, Client},
+    transport::{BlockingStream, ConnectFuture, TokioIo, Transport},
+};
+use tokio::io::{AsyncBufReadExt as _, AsyncWriteExt as _, BufReader as TokioBufReader};
+
+use super::helpers::init_logger;
+
+// In-memory CLI, answers one command per connection, or every command until `quit`
+// in interactive mode. The response to `show sess` is sent in two parts, `SLOW_DELAY` apart.
+#[derive(Debug)]
+pub(super) struct MemoryTransport;
+
+pub(super) const SLOW_DELAY: Duration = Duration::from_millis(200);
+
+fn response(line: &str) -> &'static [u8] {
+    match line.trim() {
+        "show info" => include_bytes!("../../../haproxy-stats/tests/files/2_5_5_show_info.txt"),
+        "show stat" => include_bytes!("../../../haproxy-stats/tests/files/2_5_5_show_stat.csv"),
+        "show env" => include_bytes!("../../../haproxy-stats/tests/files/2_5_5_show_env.txt"),
+        "show sess" => b"0x1: proto=unix_stream src=unix fe=GLOBAL\n0x2: proto=tcpv4 fe=http\n",
+        "prompt" | "user" | "operator" => b"",
+        _ => b"Unknown command. Please enter one of the following commands only :\n",
+    }
+}
+
+fn split_response(line: &str) -> (&'static [u8], &'static [u8]) {
+    let response = response(line);
+    match line.trim() {
+        "show sess" => response.split_at(response.len() / 2),
+        _ => (response, b""),
+    }
+}
+
+const PROMPT: &[u8] = b"\n> ";
+
+impl Transport for MemoryTransport {
+    fn connect(&self, _timeout: Option<Duration>) -> Result<BlockingStream, IoError> {
+        let (client, server) = UnixStream::pair()?;
+        thread::spawn(move || {
+            let mut reader = BufReader::new(&server);
+            let mut interactive = false;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 || line.trim() == "quit" {
+                    break;
+                }
+                interactive |= line.trim() == "prompt";
+                let (head, tail) = split_response(&line);
+                (&server).write_all(head).unwrap();
+                if !tail.is_empty() {
+                    thread::sleep(SLOW_DELAY);
+                    (&server).write_all(tail).unwrap();
+                }
+                if !interactive {
+                    break;
+                }
+                (&server).write_all(PROMPT).unwrap();
+            }
+        });
+        Ok(Box::new(client))
+    }
+
+    fn connect_async(&self, _runtime: AsyncRuntime) -> ConnectFuture<'_> {
+        Box::pin(async {
+            let (client, server) = tokio::io::duplex(4096);
+            tokio::spawn(async move {
+                let mut server = TokioBufReader::new(server);
+                let mut interactive = false;
+                loop {
+                    let mut line = String::new();
+                    if server.read_line(&mut line).await.unwrap() == 0 || line.trim() == "quit" {
+                        break;
+                    }
+                    interactive |= line.trim() == "prompt";
+                    let (head, tail) = split_response(&line);
+                    server.write_all(head).await.unwrap();
+                    if !tail.is_empty() {
+                        tokio::time::sleep(SLOW_DELAY).await;
+                        server.write_all(tail).await.unwrap();
+                    }
+                    if !interactive {
+                        break;
+                    }
+                    server.write_all(PROMPT).await.unwrap();
+                }
+            });
+            Ok(Box::new(TokioIo(client)) as _)
+        })
+    }
+}
+
+#[test]
+fn transport() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let client = Client::with_transport(MemoryTransport);
+
+    let info = client.show_info_blocking()?;
+    assert_eq!(info.pid, 8);
+
+    let statistics = client.show_stat_blocking()?;
+    assert!(!statistics.is_empty());
+
+    let vars = client.show_env_blocking()?;
+    assert!(!vars.is_empty());
+
+    let read_only_client = client.read_only();
+    let _ = read_only_client.show_info_blocking()?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transport_async() -> Result<(), Box<dyn error::Error>> {
+    init_logger();
+
+    //
+    let client = Client::with_transport(MemoryTransport);
+
+    let info = client.show_info().await?;
+    assert_eq!(info.pid, 8);
+
+    let statistics = client.show_stat().await?;
+    assert!(!statistics.is_empty());
+
+    let vars = client.show_env().await?;
+    assert!(!vars.is_empty());
+
+    let read_only_client = client.read_only();
+    let _ = read_only_client.show_info().await?;
+
+    Ok(())
+}
@@ -0,0 +1,420 @@
+use core::fmt;
+use std::io::{BufRead as _, Cursor, Error as IoError};
+
+use chrono::NaiveDate;
+use semver::Version;
+
+//
+const VERSION_PREFIXES: &[&str] = &["HAProxy version ", "HA-Proxy version "];
+
+//
+#[derive(Debug, Clone)]
+pub struct BuildInfo {
+    /// `major.minor.patch`, without the dev or distro suffix.
+    pub version: Version,
+    /// As written, e.g. `2.9-dev3` or `2.4.22-0ubuntu0.22.04.1`.
+    pub raw_version: Box<str>,
+    pub release_date: Option<NaiveDate>,
+    pub build_options: BuildOptions,
+    pub features: Vec<Feature>,
+    pub openssl_built_version: Option<Box<str>>,
+    pub openssl_running_version: Option<Box<str>>,
+    pub pcre_version: Option<Box<str>>,
+    pub lua_version: Option<Box<str>>,
+    pub polling_systems: Vec<PollingSystem>,
+    pub multiplexers: Vec<Multiplexer>,
+    pub services: Vec<Box<str>>,
+    pub filters: Vec<Filter>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BuildOptions {
+    pub target: Option<Box<str>>,
+    pub cpu: Option<Box<str>>,
+    pub cc: Option<Box<str>>,
+    pub cflags: Option<Box<str>>,
+    pub options: Vec<Box<str>>,
+    pub debug: Option<Box<str>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feature {
+    pub name: Box<str>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct PollingSystem {
+    pub name: Box<str>,
+    pub pref: Option<usize>,
+    pub usable: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Multiplexer {
+    /// `<default>` when it cannot be specified using `proto` keyword
+    pub name: Box<str>,
+    pub mode: Box<str>,
+    pub side: Box<str>,
+    pub mux: Box<str>,
+    pub flags: Vec<Box<str>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Filter {
+    pub id: Box<str>,
+    pub name: Box<str>,
+}
+
+impl BuildInfo {
+    pub fn from_vv_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, BuildInfoFromVvBytesError> {
+        let bytes = bytes.as_ref();
+
+        let cursor = Cursor::new(bytes);
+
+        let mut version = None;
+        let mut raw_version = None;
+        let mut release_date = None;
+        let mut build_options = BuildOptions::default();
+        let mut features = vec![];
+        let mut openssl_built_version = None;
+        let mut openssl_running_version = None;
+        let mut pcre_version = None;
+        let mut lua_version = None;
+        let mut polling_systems = vec![];
+        let mut multiplexers = vec![];
+        let mut services = vec![];
+        let mut filters = vec![];
+
+        let mut section = Section::None;
+        for line in cursor.lines() {
+            let line = line.map_err(BuildInfoFromVvBytesError::LinesReadFailed)?;
+            let trimmed = line.trim();
+
+            if let Some(s) = VERSION_PREFIXES.iter().find_map(|x| line.strip_prefix(x)) {
+                let mut split = s.split_whitespace();
+                let raw = split.next().unwrap_or_default();
+                version = Some(
+                    parse_version(raw)
+                        .ok_or_else(|| BuildInfoFromVvBytesError::VersionInvalid(raw.into()))?,
+                );
+                raw_version = Some(raw.into());
+                release_date = split
+                    .next()
+                    .and_then(|x| NaiveDate::parse_from_str(x, "%Y/%m/%d").ok());
+                continue;
+            }
+
+            if trimmed.is_empty() {
+                section = Section::None;
+                continue;
+            }
+
+            if let Some((k, v)) = split_colon(&line) {
+                match k {
+                    "Build options" => {
+                        section = Section::BuildOptions;
+                        continue;
+                    }
+                    "Feature list" => {
+                        features = v
+                            .split_whitespace()
+                            .filter_map(|x| {
+                                if let Some(name) = x.strip_prefix('+') {
+                                    Some(Feature {
+                                        name: name.into(),
+                                        enabled: true,
+                                    })
+                                } else {
+                                    x.strip_prefix('-').map(|name| Feature {
+                                        name: name.into(),
+                                        enabled: false,
+                                    })
+                                }
+                            })
+                            .collect();
+                        continue;
+                    }
+                    "Built with OpenSSL version" => {
+                        openssl_built_version = Some(v.into());
+                        continue;
+                    }
+                    "Running on OpenSSL version" => {
+                        openssl_running_version = Some(v.into());
+                        continue;
+                    }
+                    "Built with PCRE version" | "Built with PCRE2 version" => {
+                        pcre_version = Some(v.into());
+                        continue;
+                    }
+                    "Built with Lua version" => {
+                        lua_version = Some(v.into());
+                        continue;
+                    }
+                    "Available polling systems" => {
+                        section = Section::PollingSystems;
+                        continue;
+                    }
+                    "Available multiplexer protocols" => {
+                        section = Section::Multiplexers;
+                        continue;
+                    }
+                    "Available services" => {
+                        services = v
+                            .split_whitespace()
+                            .filter(|x| *x != "none")
+                            .map(Into::into)
+                            .collect();
+                        section = Section::Services;
+                        continue;
+                    }
+                    "Available filters" => {
+                        section = Section::Filters;
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+
+            match section {
+                Section::None => {}
+                Section::BuildOptions => {
+                    if let Some((k, v)) = trimmed.split_once('=') {
+                        let v = v.trim();
+                        let v: Option<Box<str>> = if v.is_empty() { None } else { Some(v.into()) };
+                        match k.trim() {
+                            "TARGET" => build_options.target = v,
+                            "CPU" => build_options.cpu = v,
+                            "CC" => build_options.cc = v,
+                            "CFLAGS" => build_options.cflags = v,
+                            "OPTIONS" => {
+                                build_options.options = v
+                                    .unwrap_or_default()
+                                    .split_whitespace()
+                                    .map(Into::into)
+                                    .collect()
+                            }
+                            "DEBUG" => build_options.debug = v,
+                            _ => {}
+                        }
+                    }
+                }
+                Section::PollingSystems => {
+                    if let Some((name, v)) = split_colon(&line) {
+                        let pref = v
+                            .split(',')
+                            .next()
+                            .and_then(|x| x.trim().strip_prefix("pref="))
+                            .and_then(|x| x.parse().ok());
+                        polling_systems.push(PollingSystem {
+                            name: name.into(),
+                            pref,
+                            usable: v.ends_with("test result OK"),
+                        });
+                    }
+                }
+                Section::Multiplexers => {
+                    if let Some((name, v)) = split_colon(&line) {
+                        let mut multiplexer = Multiplexer {
+                            name: name.into(),
+                            mode: "".into(),
+                            side: "".into(),
+                            mux: "".into(),
+                            flags: vec![],
+                        };
+                        for (k, v) in v.split_whitespace().filter_map(|x| x.split_once('=')) {
+                            match k {
+                                "mode" => multiplexer.mode = v.into(),
+                                "side" => multiplexer.side = v.into(),
+                                "mux" => multiplexer.mux = v.into(),
+                                "flags" => {
+                                    multiplexer.flags = v
+                                        .split('|')
+                                        .filter(|x| !x.is_empty())
+                                        .map(Into::into)
+                                        .collect()
+                                }
+                                _ => {}
+                            }
+                        }
+                        multiplexers.push(multiplexer);
+                    }
+                }
+                Section::Services => {}
+                Section::Filters => {
+                    if let Some((id, name)) =
+                        trimmed.strip_prefix('[').and_then(|x| x.split_once(']'))
+                    {
+                        filters.push(Filter {
+                            id: id.into(),
+                            name: name.trim().into(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            version: version.ok_or(BuildInfoFromVvBytesError::VersionMissing)?,
+            raw_version: raw_version.unwrap_or_default(),
+            release_date,
+            build_options,
+            features,
+            openssl_built_version,
+            openssl_running_version,
+            pcre_version,
+            lua_version,
+            polling_systems,
+            multiplexers,
+            services,
+            filters,
+        })
+    }
+
+    pub fn enabled_features(&self) -> impl Iterator<Item = &str> {
+        self.features
+            .iter()
+            .filter(|x| x.enabled)
+            .map(|x| x.name.as_ref())
+    }
+
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.enabled_features().any(|x| x == name)
+    }
+}
+
+//
+enum Section {
+    None,
+    BuildOptions,
+    PollingSystems,
+    Multiplexers,
+    Services,
+    Filters,
+}
+
+// e.g. `2.5.5-384c5c5`, `2.9-dev3` or `2.4.22-0ubuntu0.22.04.1`, the patch defaults to 0.
+fn parse_version(s: &str) -> Option<Version> {
+    let end = s
+        .find(|x: char| !(x.is_ascii_digit() || x == '.'))
+        .unwrap_or(s.len());
+    let mut parts = s[..end].split('.').map(|x| x.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next()??;
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some(Version::new(major, minor, patch))
+}
+
+fn split_colon(line: &str) -> Option<(&str, &str)> {
+    line.split_once(" : ")
+        .or_else(|| line.strip_suffix(" :").map(|x| (x, "")))
+        .map(|(k, v)| (k.trim(), v.trim()))
+}
+
+//
+#[derive(Debug)]
+pub enum BuildInfoFromVvBytesError {
+    LinesReadFailed(IoError),
+    VersionMissing,
+    VersionInvalid(Box<str>),
+}
+
+impl fmt::Display for BuildInfoFromVvBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for BuildInfoFromVvBytesError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_info_from_vv_bytes() {
+        let bytes = include_bytes!("../tests/files/2_5_5_haproxy_vv.txt");
+
+        let build_info = BuildInfo::from_vv_bytes(bytes).unwrap();
+
+        assert_eq!(build_info.version, Version::new(2, 5, 5));
+        assert_eq!(build_info.raw_version.as_ref(), "2.5.5-384c5c5");
+        assert_eq!(
+            build_info.release_date,
+            NaiveDate::from_ymd_opt(2022, 3, 14)
+        );
+        assert_eq!(build_info.build_options.target, Some("linux-musl".into()));
+        assert_eq!(build_info.build_options.options.len(), 6);
+        assert_eq!(build_info.build_options.debug, None);
+
+        assert!(build_info.has_feature("OPENSSL"));
+        assert!(build_info.has_feature("PROMEX"));
+        assert!(!build_info.has_feature("QUIC"));
+        assert!(build_info.features.contains(&Feature {
+            name: "QUIC".into(),
+            enabled: false
+        }));
+
+        assert_eq!(
+            build_info.openssl_built_version,
+            Some("OpenSSL 1.1.1n  15 Mar 2022".into())
+        );
+        assert_eq!(build_info.pcre_version, Some("10.39 2021-10-29".into()));
+        assert_eq!(build_info.lua_version, Some("Lua 5.3.6".into()));
+
+        assert_eq!(build_info.polling_systems.len(), 3);
+        assert_eq!(build_info.polling_systems[0].name, "epoll".into());
+        assert_eq!(build_info.polling_systems[0].pref, Some(300));
+        assert!(build_info.polling_systems[0].usable);
+
+        assert_eq!(build_info.multiplexers.len(), 6);
+        assert_eq!(build_info.multiplexers[0].name, "h2".into());
+        assert_eq!(build_info.multiplexers[0].mux, "H2".into());
+        assert_eq!(build_info.multiplexers[0].flags.len(), 4);
+        assert!(build_info.multiplexers[4].flags.is_empty());
+
+        assert_eq!(build_info.services, vec!["prometheus-exporter".into()]);
+
+        assert_eq!(build_info.filters.len(), 5);
+        assert_eq!(build_info.filters[0].id, "SPOE".into());
+        assert_eq!(build_info.filters[2].name, "fcgi-app".into());
+    }
+
+    #[test]
+    fn test_build_info_from_vv_bytes_with_version_suffix() {
+        for (line, version) in [
+            (
+                "HAProxy version 2.9-dev3 2023/08/11 - https://haproxy.org/\n",
+                Version::new(2, 9, 0),
+            ),
+            (
+                "HAProxy version 2.4.22-0ubuntu0.22.04.1 2023/02/14 - https://haproxy.org/\n",
+                Version::new(2, 4, 22),
+            ),
+            (
+                "HA-Proxy version 1.8.19 2019/02/11\n",
+                Version::new(1, 8, 19),
+            ),
+        ] {
+            let build_info = BuildInfo::from_vv_bytes(line).unwrap();
+            assert_eq!(build_info.version, version);
+            assert_eq!(
+                build_info.raw_version.as_ref(),
+                line.split_whitespace().nth(2).unwrap()
+            );
+        }
+
+        match BuildInfo::from_vv_bytes(b"HAProxy version dev\n") {
+            Err(BuildInfoFromVvBytesError::VersionInvalid(raw)) => assert_eq!(raw.as_ref(), "dev"),
+            x => panic!("{:?}", x),
+        }
+    }
+
+    #[test]
+    fn test_build_info_from_vv_bytes_with_version_missing() {
+        match BuildInfo::from_vv_bytes(b"Unknown command.\n") {
+            Err(BuildInfoFromVvBytesError::VersionMissing) => {}
+            x => panic!("{:?}", x),
+        }
+    }
+}
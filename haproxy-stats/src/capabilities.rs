@@ -0,0 +1,199 @@
+use semver::Version;
+
+use crate::{build_info::BuildInfo, info::Info};
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// `show info typed`, `show stat typed`
+    TypedOutput,
+    /// `show info json`, `show stat json`
+    JsonOutput,
+    /// `show env`
+    ShowEnv,
+    /// `show cli level`, `operator`, `user`
+    CliLevel,
+    /// `expert-mode`
+    ExpertMode,
+    /// `show events`
+    ShowEvents,
+    /// `show servers conn`
+    ShowServersConn,
+    /// `experimental-mode`
+    ExperimentalMode,
+    /// `add server`, `del server`
+    DynamicServers,
+    /// QUIC counters in `show stat`, requires build with QUIC
+    QuicCounters,
+    /// `wait`
+    Wait,
+    /// `reload` on the master CLI responds with the status and the startup logs
+    ReloadStatus,
+}
+
+impl Capability {
+    pub const ALL: &'static [Capability] = &[
+        Self::TypedOutput,
+        Self::JsonOutput,
+        Self::ShowEnv,
+        Self::CliLevel,
+        Self::ExpertMode,
+        Self::ShowEvents,
+        Self::ShowServersConn,
+        Self::ExperimentalMode,
+        Self::DynamicServers,
+        Self::QuicCounters,
+        Self::Wait,
+        Self::ReloadStatus,
+    ];
+
+    /// (major, minor)
+    pub fn min_version(&self) -> (u64, u64) {
+        match self {
+            Self::TypedOutput => (1, 7),
+            Self::JsonOutput => (1, 8),
+            Self::ShowEnv => (1, 8),
+            Self::CliLevel => (1, 9),
+            Self::ExpertMode => (2, 1),
+            Self::ShowEvents => (2, 1),
+            Self::ShowServersConn => (2, 2),
+            Self::ExperimentalMode => (2, 4),
+            Self::DynamicServers => (2, 4),
+            Self::QuicCounters => (2, 6),
+            Self::Wait => (2, 7),
+            Self::ReloadStatus => (2, 7),
+        }
+    }
+
+    /// Name in the `Feature list` of `haproxy -vv`, without the `+`.
+    pub fn required_build_feature(&self) -> Option<&'static str> {
+        match self {
+            Self::QuicCounters => Some("QUIC"),
+            _ => None,
+        }
+    }
+}
+
+//
+#[derive(Debug, Clone)]
+pub struct Capabilities {
+    version: Version,
+    build_features: Option<Vec<Box<str>>>,
+}
+
+impl Capabilities {
+    pub fn new(version: Version) -> Self {
+        Self {
+            version,
+            build_features: None,
+        }
+    }
+
+    pub fn from_info(info: &Info) -> Self {
+        Self::new(info.version.clone())
+    }
+
+    pub fn from_build_info(build_info: &BuildInfo) -> Self {
+        Self::new(build_info.version.clone()).with_build_info(build_info)
+    }
+
+    pub fn with_build_info(self, build_info: &BuildInfo) -> Self {
+        self.with_build_features(build_info.enabled_features())
+    }
+
+    /// Enabled build features, e.g. `["OPENSSL", "LUA", "QUIC"]`.
+    /// Without them, capabilities requiring a build feature are reported as unsupported.
+    pub fn with_build_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.build_features = Some(
+            features
+                .into_iter()
+                .map(|x| x.as_ref().trim_start_matches('+').into())
+                .collect(),
+        );
+        self
+    }
+
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    pub fn has_build_feature(&self, feature: &str) -> Option<bool> {
+        self.build_features
+            .as_ref()
+            .map(|x| x.iter().any(|y| y.as_ref() == feature))
+    }
+
+    pub fn supports(&self, capability: Capability) -> bool {
+        // Compare major and minor only, "2.5.5-384c5c5" is a pre-release in semver.
+        if (self.version.major, self.version.minor) < capability.min_version() {
+            return false;
+        }
+
+        match capability.required_build_feature() {
+            Some(feature) => self.has_build_feature(feature).unwrap_or(false),
+            None => true,
+        }
+    }
+
+    pub fn supported(&self) -> Vec<Capability> {
+        Capability::ALL
+            .iter()
+            .copied()
+            .filter(|x| self.supports(*x))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_from_info() {
+        let bytes = include_bytes!("../tests/files/2_5_5_show_info.txt");
+
+        let info = Info::from_kv_bytes(bytes).unwrap();
+
+        let capabilities = Capabilities::from_info(&info);
+
+        assert!(capabilities.supports(Capability::JsonOutput));
+        assert!(capabilities.supports(Capability::DynamicServers));
+        assert!(!capabilities.supports(Capability::Wait));
+        assert!(!capabilities.supports(Capability::QuicCounters));
+    }
+
+    #[test]
+    fn test_capabilities_with_build_features() {
+        let capabilities = Capabilities::new(Version::new(2, 7, 0));
+        assert!(capabilities.supports(Capability::Wait));
+        assert!(!capabilities.supports(Capability::QuicCounters));
+
+        let capabilities = capabilities.with_build_features(["+OPENSSL", "+QUIC"]);
+        assert!(capabilities.supports(Capability::QuicCounters));
+        assert_eq!(capabilities.supported().len(), Capability::ALL.len());
+
+        let capabilities = Capabilities::new(Version::new(2, 5, 0)).with_build_features(["QUIC"]);
+        assert!(!capabilities.supports(Capability::QuicCounters));
+    }
+
+    #[test]
+    fn test_capabilities_with_build_info() {
+        let info =
+            Info::from_kv_bytes(include_bytes!("../tests/files/2_5_5_show_info.txt")).unwrap();
+        let build_info =
+            BuildInfo::from_vv_bytes(include_bytes!("../tests/files/2_5_5_haproxy_vv.txt"))
+                .unwrap();
+
+        let capabilities = Capabilities::from_info(&info).with_build_info(&build_info);
+        assert_eq!(capabilities.has_build_feature("OPENSSL"), Some(true));
+        assert_eq!(capabilities.has_build_feature("QUIC"), Some(false));
+
+        let capabilities = Capabilities::from_build_info(&build_info);
+        assert!(capabilities.supports(Capability::DynamicServers));
+        assert!(!capabilities.supports(Capability::QuicCounters));
+    }
+}
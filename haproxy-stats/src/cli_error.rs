@@ -0,0 +1,107 @@
+use core::fmt;
+
+//
+// (prefix of the first line, kind), e.g. "No such backend.", "Can't find server."
+const PATTERNS: &[(&str, CliErrorKind)] = &[
+    ("Permission denied", CliErrorKind::PermissionDenied),
+    ("Unknown command", CliErrorKind::UnknownCommand),
+    ("No such ", CliErrorKind::NotFound),
+    ("Can't find ", CliErrorKind::NotFound),
+    ("Unknown backend", CliErrorKind::NotFound),
+    ("Unknown frontend", CliErrorKind::NotFound),
+    ("Require ", CliErrorKind::InvalidArgument),
+    ("Invalid ", CliErrorKind::InvalidArgument),
+    ("Missing ", CliErrorKind::InvalidArgument),
+    ("Unexpected ", CliErrorKind::InvalidArgument),
+];
+
+//
+/// Error response of the CLI, e.g. `Permission denied`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliError {
+    pub kind: CliErrorKind,
+    /// The whole response, trimmed.
+    pub message: Box<str>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CliErrorKind {
+    PermissionDenied,
+    UnknownCommand,
+    NotFound,
+    InvalidArgument,
+}
+
+impl CliError {
+    /// `None` if the response doesn't start with a known error message.
+    pub fn from_response_bytes(bytes: impl AsRef<[u8]>) -> Option<Self> {
+        let bytes = bytes.as_ref();
+
+        let first_line = bytes.split(|x| *x == b'\n').next().unwrap_or_default();
+        let first_line = core::str::from_utf8(first_line).ok()?;
+        let first_line = strip_severity(first_line.trim_start());
+
+        let kind = PATTERNS
+            .iter()
+            .find(|(prefix, _)| first_line.starts_with(prefix))
+            .map(|(_, kind)| *kind)?;
+
+        Some(Self {
+            kind,
+            message: String::from_utf8_lossy(bytes).trim().into(),
+        })
+    }
+}
+
+// With `set severity-output number`, e.g. "[3]: Permission denied"
+fn strip_severity(s: &str) -> &str {
+    match s.strip_prefix('[').and_then(|x| x.split_once("]: ")) {
+        Some((severity, rest)) if !severity.is_empty() => rest,
+        _ => s,
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_error_from_response_bytes() {
+        for (bytes, kind) in [
+            (&b"Permission denied\n\n"[..], CliErrorKind::PermissionDenied),
+            (
+                &b"Unknown command. Please enter one of the following commands only :\n  help           : this message\n"[..],
+                CliErrorKind::UnknownCommand,
+            ),
+            (&b"No such backend.\n"[..], CliErrorKind::NotFound),
+            (&b"Can't find server.\n"[..], CliErrorKind::NotFound),
+            (
+                &b"Require 'backend/server'.\n"[..],
+                CliErrorKind::InvalidArgument,
+            ),
+            (&b"[3]: Permission denied\n"[..], CliErrorKind::PermissionDenied),
+        ] {
+            let err = CliError::from_response_bytes(bytes).unwrap();
+            assert_eq!(err.kind, kind);
+            assert!(!err.message.ends_with('\n'));
+        }
+
+        assert!(CliError::from_response_bytes(b"").is_none());
+        assert!(CliError::from_response_bytes(include_bytes!(
+            "../tests/files/2_5_5_show_info.txt"
+        ))
+        .is_none());
+        assert!(CliError::from_response_bytes(include_bytes!(
+            "../tests/files/2_5_5_show_stat.csv"
+        ))
+        .is_none());
+    }
+}
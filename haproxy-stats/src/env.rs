@@ -1,15 +1,29 @@
 use core::{fmt, ops::Deref};
-use std::{
-    collections::HashMap,
-    io::{BufRead as _, Cursor, Error as IoError},
-};
+use std::io::{BufRead as _, Cursor, Error as IoError};
+
+use indexmap::IndexMap;
+
+//
+pub const HAPROXY_LOCALPEER: &str = "HAPROXY_LOCALPEER";
+pub const HAPROXY_CFGFILES: &str = "HAPROXY_CFGFILES";
+pub const HAPROXY_MWORKER: &str = "HAPROXY_MWORKER";
+pub const HAPROXY_CLI: &str = "HAPROXY_CLI";
+pub const HAPROXY_MASTER_CLI: &str = "HAPROXY_MASTER_CLI";
+pub const HAPROXY_VERSION: &str = "HAPROXY_VERSION";
+pub const HAPROXY_STARTUP_VERSION: &str = "HAPROXY_STARTUP_VERSION";
+pub const HAPROXY_BRANCH: &str = "HAPROXY_BRANCH";
+pub const HAPROXY_HTTP_LOG_FMT: &str = "HAPROXY_HTTP_LOG_FMT";
+pub const HAPROXY_HTTPS_LOG_FMT: &str = "HAPROXY_HTTPS_LOG_FMT";
+pub const HAPROXY_TCP_LOG_FMT: &str = "HAPROXY_TCP_LOG_FMT";
+
+const LIST_SEPARATOR: char = ';';
 
 //
 #[derive(Debug, Clone)]
-pub struct EnvironmentVariables(pub HashMap<Box<str>, Box<str>>);
+pub struct EnvironmentVariables(pub IndexMap<Box<str>, Box<str>>);
 
 impl Deref for EnvironmentVariables {
-    type Target = HashMap<Box<str>, Box<str>>;
+    type Target = IndexMap<Box<str>, Box<str>>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -24,22 +38,84 @@ impl EnvironmentVariables {
 
         let cursor = Cursor::new(bytes);
 
-        let map: HashMap<Box<str>, Box<str>> = cursor
-            .lines()
-            .map(|x| match x {
-                Ok(s) => {
-                    let mut split = s.split('=');
-                    let k: Box<str> = split.next().unwrap_or_default().into();
-                    let v: Box<str> = split.next().unwrap_or_default().into();
+        let mut map: IndexMap<Box<str>, Box<str>> = IndexMap::new();
+        // Empty lines are kept only when a line of the same value follows,
+        // the response ends with an empty line.
+        let mut empty_lines = 0;
+        for line in cursor.lines() {
+            let line = line.map_err(EnvironmentVariablesFromKvBytesError::LinesReadFailed)?;
+            if line.is_empty() {
+                empty_lines += 1;
+                continue;
+            }
 
-                    Ok((k, v))
+            // Only the first '=' separates, values may contain '='.
+            match line.split_once('=') {
+                Some((k, v)) => {
+                    map.insert(k.into(), v.into());
+                }
+                // A line of a multi-line value, skipped before the first variable.
+                None => {
+                    if let Some((_, v)) = map.last_mut() {
+                        *v = format!("{}{}{}", v, "\n".repeat(empty_lines + 1), line).into();
+                    }
                 }
-                Err(err) => Err(EnvironmentVariablesFromKvBytesError::LinesReadFailed(err)),
-            })
-            .collect::<Result<_, _>>()?;
+            }
+            empty_lines = 0;
+        }
 
         Ok(Self(map))
     }
+
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(|x| x.as_ref())
+    }
+
+    pub fn haproxy(&self) -> HaproxyEnvironmentVariables {
+        let list = |name: &str| -> Vec<Box<str>> {
+            self.get_str(name)
+                .map(|x| {
+                    x.split(LIST_SEPARATOR)
+                        .filter(|y| !y.is_empty())
+                        .map(Into::into)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let value = |name: &str| -> Option<Box<str>> { self.get_str(name).map(Into::into) };
+
+        HaproxyEnvironmentVariables {
+            localpeer: value(HAPROXY_LOCALPEER),
+            cfgfiles: list(HAPROXY_CFGFILES),
+            mworker: self.get_str(HAPROXY_MWORKER) == Some("1"),
+            cli: list(HAPROXY_CLI),
+            master_cli: list(HAPROXY_MASTER_CLI),
+            version: value(HAPROXY_VERSION),
+            startup_version: value(HAPROXY_STARTUP_VERSION),
+            branch: value(HAPROXY_BRANCH),
+            http_log_fmt: value(HAPROXY_HTTP_LOG_FMT),
+            https_log_fmt: value(HAPROXY_HTTPS_LOG_FMT),
+            tcp_log_fmt: value(HAPROXY_TCP_LOG_FMT),
+        }
+    }
+}
+
+//
+/// The variables HAProxy sets itself.
+#[derive(Debug, Clone, Default)]
+pub struct HaproxyEnvironmentVariables {
+    pub localpeer: Option<Box<str>>,
+    pub cfgfiles: Vec<Box<str>>,
+    pub mworker: bool,
+    /// e.g. `unix@/var/run/haproxy.sock`, `ipv4@0.0.0.0:9255`, `sockpair@4`
+    pub cli: Vec<Box<str>>,
+    pub master_cli: Vec<Box<str>>,
+    pub version: Option<Box<str>>,
+    pub startup_version: Option<Box<str>>,
+    pub branch: Option<Box<str>>,
+    pub http_log_fmt: Option<Box<str>>,
+    pub https_log_fmt: Option<Box<str>>,
+    pub tcp_log_fmt: Option<Box<str>>,
 }
 
 //
@@ -70,5 +146,60 @@ mod tests {
             vars.get("HAPROXY_VERSION").cloned().unwrap(),
             "2.5.5".into()
         );
+
+        assert_eq!(vars.len(), 17);
+        assert_eq!(vars.get_index(0).unwrap().0.as_ref(), "HOSTNAME");
+        assert_eq!(vars.get_index(16).unwrap().0.as_ref(), "HAPROXY_CLI");
+
+        let haproxy_vars = vars.haproxy();
+        assert_eq!(haproxy_vars.localpeer, Some("caf3d1af2d97".into()));
+        assert_eq!(
+            haproxy_vars.cfgfiles,
+            vec!["/usr/local/etc/haproxy/haproxy.cfg".into()]
+        );
+        assert!(haproxy_vars.mworker);
+        assert_eq!(
+            haproxy_vars.cli,
+            vec![
+                "unix@/var/run/haproxy.sock".into(),
+                "ipv4@0.0.0.0:9255".into(),
+                "sockpair@4".into()
+            ]
+        );
+        assert!(haproxy_vars.master_cli.is_empty());
+        assert_eq!(haproxy_vars.branch, None);
+    }
+
+    #[test]
+    fn test_env_from_kv_bytes_with_value_contains_equals_sign() {
+        let bytes = b"HAPROXY_HTTP_LOG_FMT=%ci:%cp [%tr] %ft %b/%s %TR/%Tw/%Tc/%Tr/%Ta %ST %B %CC %CS %tsc %ac/%fc/%bc/%sc/%rc %sq/%bq %hr %hs %{+Q}r\nFOO=a=b=c\n";
+
+        let vars = EnvironmentVariables::from_kv_bytes(bytes).unwrap();
+
+        assert_eq!(vars.get_str("FOO"), Some("a=b=c"));
+        assert!(vars.haproxy().http_log_fmt.unwrap().ends_with("%{+Q}r"));
+    }
+
+    #[test]
+    fn test_env_from_kv_bytes_with_multi_line_value() {
+        let bytes = b"continued\nFOO=first\nsecond\nBAR=1\n";
+
+        let vars = EnvironmentVariables::from_kv_bytes(bytes).unwrap();
+
+        assert_eq!(vars.len(), 2);
+        assert_eq!(vars.get_str("FOO"), Some("first\nsecond"));
+        assert_eq!(vars.get_str("BAR"), Some("1"));
+    }
+
+    #[test]
+    fn test_env_from_kv_bytes_with_empty_line_in_value() {
+        let bytes = b"FOO=first\n\nthird\nBAR=1\n\nBAZ=2\n\n";
+
+        let vars = EnvironmentVariables::from_kv_bytes(bytes).unwrap();
+
+        assert_eq!(vars.len(), 3);
+        assert_eq!(vars.get_str("FOO"), Some("first\n\nthird"));
+        assert_eq!(vars.get_str("BAR"), Some("1"));
+        assert_eq!(vars.get_str("BAZ"), Some("2"));
     }
 }
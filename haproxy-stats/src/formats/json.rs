@@ -1,5 +1,6 @@
-use serde::Deserialize;
-use serde_json::Value as SerdeJsonValue;
+use serde::{de, Deserialize, Deserializer};
+use serde_enum_str::Deserialize_enum_str;
+use serde_json::{Number, Value as SerdeJsonValue};
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct Field {
@@ -9,9 +10,48 @@ pub struct Field {
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct Tags {
-    pub origin: Box<str>,
-    pub nature: Box<str>,
-    pub scope: Box<str>,
+    pub origin: Origin,
+    pub nature: Nature,
+    pub scope: Scope,
+}
+
+#[derive(Deserialize_enum_str, Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    Metric,
+    Status,
+    Key,
+    Config,
+    Product,
+    #[serde(other)]
+    Other(String),
+}
+
+#[derive(Deserialize_enum_str, Debug, Clone, PartialEq, Eq)]
+pub enum Nature {
+    Gauge,
+    Limit,
+    Min,
+    Max,
+    Rate,
+    Counter,
+    Duration,
+    Age,
+    Time,
+    Name,
+    Output,
+    Avg,
+    #[serde(other)]
+    Other(String),
+}
+
+#[derive(Deserialize_enum_str, Debug, Clone, PartialEq, Eq)]
+pub enum Scope {
+    Process,
+    Service,
+    System,
+    Cluster,
+    #[serde(other)]
+    Other(String),
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -23,10 +63,15 @@ pub enum Value {
     S64(i64),
     #[serde(rename = "u32")]
     U32(u32),
-    #[serde(rename = "u64")]
+    // Some versions emit large u64 as string.
+    #[serde(rename = "u64", deserialize_with = "deserialize_u64_or_str")]
     U64(u64),
+    #[serde(rename = "flt")]
+    Flt(f64),
     #[serde(rename = "str")]
     Str(Box<str>),
+    #[serde(rename = "empty")]
+    Empty,
 }
 
 impl Value {
@@ -60,6 +105,13 @@ impl Value {
         }
     }
 
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Flt(v) => Some(*v),
+            _ => None,
+        }
+    }
+
     pub fn as_str(&self) -> Option<&str> {
         match self {
             Self::Str(v) => Some(v),
@@ -67,13 +119,19 @@ impl Value {
         }
     }
 
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
     pub fn value_to_string(&self) -> String {
         match self {
             Self::S32(v) => v.to_string(),
             Self::S64(v) => v.to_string(),
             Self::U32(v) => v.to_string(),
             Self::U64(v) => v.to_string(),
+            Self::Flt(v) => v.to_string(),
             Self::Str(v) => v.to_string(),
+            Self::Empty => "".to_string(),
         }
     }
 }
@@ -85,7 +143,67 @@ impl From<&Value> for SerdeJsonValue {
             Value::S64(v) => SerdeJsonValue::Number((*v).into()),
             Value::U32(v) => SerdeJsonValue::Number((*v).into()),
             Value::U64(v) => SerdeJsonValue::Number((*v).into()),
+            Value::Flt(v) => Number::from_f64(*v)
+                .map(SerdeJsonValue::Number)
+                .unwrap_or(SerdeJsonValue::Null),
             Value::Str(v) => SerdeJsonValue::String(v.to_string()),
+            Value::Empty => SerdeJsonValue::Null,
         }
     }
 }
+
+fn deserialize_u64_or_str<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum U64OrStr {
+        U64(u64),
+        Str(Box<str>),
+    }
+
+    match U64OrStr::deserialize(deserializer)? {
+        U64OrStr::U64(v) => Ok(v),
+        U64OrStr::Str(s) => s.parse().map_err(de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize, Debug)]
+    struct Item {
+        tags: Tags,
+        value: Value,
+    }
+
+    #[test]
+    fn test_de() {
+        let item: Item = serde_json::from_str(
+            r#"{"tags":{"origin":"Metric","nature":"Avg","scope":"Process"},"value":{"type":"flt","value":0.250000}}"#,
+        )
+        .unwrap();
+        assert_eq!(item.tags.origin, Origin::Metric);
+        assert_eq!(item.tags.nature, Nature::Avg);
+        assert_eq!(item.tags.scope, Scope::Process);
+        assert_eq!(item.value.as_f64(), Some(0.25));
+
+        let item: Item = serde_json::from_str(
+            r#"{"tags":{"origin":"Config","nature":"Limit","scope":"Cluster"},"value":{"type":"u64","value":"18446744073709551615"}}"#,
+        )
+        .unwrap();
+        assert_eq!(item.tags.scope, Scope::Cluster);
+        assert_eq!(item.value.as_u64(), Some(u64::MAX));
+
+        let item: Item = serde_json::from_str(
+            r#"{"tags":{"origin":"Foo","nature":"Bar","scope":"Service"},"value":{"type":"empty"}}"#,
+        )
+        .unwrap();
+        assert_eq!(item.tags.origin, Origin::Other("Foo".into()));
+        assert_eq!(item.tags.nature, Nature::Other("Bar".into()));
+        assert!(item.value.is_empty());
+        assert_eq!(SerdeJsonValue::from(&item.value), SerdeJsonValue::Null);
+    }
+}
@@ -0,0 +1,91 @@
+use core::{fmt, str::FromStr};
+
+use crate::command::Command;
+
+//
+/// Access level of a CLI session, see `stats socket ... level`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CliLevel {
+    User,
+    Operator,
+    Admin,
+}
+
+impl CliLevel {
+    /// Response of `show cli level`.
+    pub fn from_response_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, CliLevelParseError> {
+        let s = String::from_utf8_lossy(bytes.as_ref());
+        s.trim().parse()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Operator => "operator",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// Lowers the level of the session, `None` for admin, it cannot be raised.
+    pub fn to_command(&self) -> Option<Command> {
+        match self {
+            Self::User => Some(Command::user()),
+            Self::Operator => Some(Command::operator()),
+            Self::Admin => None,
+        }
+    }
+}
+
+impl FromStr for CliLevel {
+    type Err = CliLevelParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(Self::User),
+            "operator" => Ok(Self::Operator),
+            "admin" => Ok(Self::Admin),
+            s => Err(CliLevelParseError::Unknown(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for CliLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum CliLevelParseError {
+    Unknown(Box<str>),
+}
+
+impl fmt::Display for CliLevelParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for CliLevelParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_level() {
+        assert_eq!(
+            CliLevel::from_response_bytes(b"operator\n").unwrap(),
+            CliLevel::Operator
+        );
+        assert!(CliLevel::User < CliLevel::Operator);
+        assert!(CliLevel::Operator < CliLevel::Admin);
+        assert!(CliLevel::Admin.to_command().is_none());
+
+        match CliLevel::from_response_bytes(b"Unknown command.\n") {
+            Err(CliLevelParseError::Unknown(_)) => {}
+            x => panic!("{:?}", x),
+        }
+    }
+}
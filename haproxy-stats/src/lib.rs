@@ -1,13 +1,30 @@
 //
+pub mod build_info;
+pub mod capabilities;
+pub mod cli_error;
 pub mod command;
 pub mod env;
 pub mod info;
+pub mod level;
+pub mod metadata;
+pub mod mode;
+pub mod proc;
+pub mod reload;
 pub mod stat;
+pub mod wait;
 
-pub use command::{Command, Commands};
+pub use build_info::BuildInfo;
+pub use capabilities::{Capabilities, Capability};
+pub use cli_error::{CliError, CliErrorKind};
+pub use command::{Command, Commands, ReadOnlyCommand};
 pub use env::EnvironmentVariables;
 pub use info::Info;
+pub use level::CliLevel;
+pub use mode::CliMode;
+pub use proc::{Process, Processes};
+pub use reload::ReloadStatus;
 pub use stat::{Statistic, Statistics};
+pub use wait::WaitStatus;
 
 //
 pub mod formats;
@@ -0,0 +1,943 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use crate::formats::json::{Nature, Origin, Scope, Tags};
+
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+}
+
+impl MetricKind {
+    /// Keys, names and product outputs are not metrics.
+    pub fn from_origin_and_nature(origin: &Origin, nature: &Nature) -> Option<Self> {
+        match (origin, nature) {
+            (Origin::Key | Origin::Product, _) => None,
+            (_, Nature::Name | Nature::Output) => None,
+            (_, Nature::Counter) => Some(Self::Counter),
+            _ => Some(Self::Gauge),
+        }
+    }
+
+    pub fn from_tags(tags: &Tags) -> Option<Self> {
+        Self::from_origin_and_nature(&tags.origin, &tags.nature)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    None,
+    Bytes,
+    Seconds,
+    Milliseconds,
+    PerSecond,
+}
+
+//
+#[derive(Debug)]
+pub struct FieldMetadata {
+    pub name: &'static str,
+    pub origin: Origin,
+    pub nature: Nature,
+    pub scope: Scope,
+    pub unit: Unit,
+    pub description: &'static str,
+}
+
+impl FieldMetadata {
+    const fn new(
+        name: &'static str,
+        origin: Origin,
+        nature: Nature,
+        scope: Scope,
+        unit: Unit,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            origin,
+            nature,
+            scope,
+            unit,
+            description,
+        }
+    }
+
+    pub fn kind(&self) -> Option<MetricKind> {
+        MetricKind::from_origin_and_nature(&self.origin, &self.nature)
+    }
+}
+
+//
+/// Metadata of the `show stat` fields, tags are the same as `show stat json` emits.
+pub static STAT_FIELDS: &[FieldMetadata] = &[
+    FieldMetadata::new(
+        "pxname",
+        Origin::Key,
+        Nature::Name,
+        Scope::Service,
+        Unit::None,
+        "proxy name",
+    ),
+    FieldMetadata::new(
+        "svname",
+        Origin::Key,
+        Nature::Name,
+        Scope::Service,
+        Unit::None,
+        "service name (FRONTEND, BACKEND or server/listener name)",
+    ),
+    FieldMetadata::new(
+        "qcur",
+        Origin::Metric,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "current queued requests",
+    ),
+    FieldMetadata::new(
+        "qmax",
+        Origin::Metric,
+        Nature::Max,
+        Scope::Process,
+        Unit::None,
+        "max value of qcur",
+    ),
+    FieldMetadata::new(
+        "scur",
+        Origin::Metric,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "current sessions",
+    ),
+    FieldMetadata::new(
+        "smax",
+        Origin::Metric,
+        Nature::Max,
+        Scope::Process,
+        Unit::None,
+        "max sessions",
+    ),
+    FieldMetadata::new(
+        "slim",
+        Origin::Config,
+        Nature::Limit,
+        Scope::Process,
+        Unit::None,
+        "configured session limit",
+    ),
+    FieldMetadata::new(
+        "stot",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "cumulative number of sessions",
+    ),
+    FieldMetadata::new(
+        "bin",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::Bytes,
+        "bytes in",
+    ),
+    FieldMetadata::new(
+        "bout",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::Bytes,
+        "bytes out",
+    ),
+    FieldMetadata::new(
+        "dreq",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "requests denied because of security concerns",
+    ),
+    FieldMetadata::new(
+        "dresp",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "responses denied because of security concerns",
+    ),
+    FieldMetadata::new(
+        "ereq",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "request errors",
+    ),
+    FieldMetadata::new(
+        "econ",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "number of requests that encountered an error trying to connect to a backend server",
+    ),
+    FieldMetadata::new(
+        "eresp",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "response errors",
+    ),
+    FieldMetadata::new(
+        "wretr",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "number of times a connection to a server was retried",
+    ),
+    FieldMetadata::new(
+        "wredis",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "number of times a request was redispatched to another server",
+    ),
+    FieldMetadata::new(
+        "status",
+        Origin::Status,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "status (UP/DOWN/NOLB/MAINT/MAINT(via)/MAINT(resolution)...)",
+    ),
+    FieldMetadata::new(
+        "weight",
+        Origin::Metric,
+        Nature::Avg,
+        Scope::Process,
+        Unit::None,
+        "total effective weight (backend), effective weight (server)",
+    ),
+    FieldMetadata::new(
+        "act",
+        Origin::Status,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "number of active servers (backend), server is active (server)",
+    ),
+    FieldMetadata::new(
+        "bck",
+        Origin::Status,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "number of backup servers (backend), server is backup (server)",
+    ),
+    FieldMetadata::new(
+        "chkfail",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "number of failed checks",
+    ),
+    FieldMetadata::new(
+        "chkdown",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "number of UP->DOWN transitions",
+    ),
+    FieldMetadata::new(
+        "lastchg",
+        Origin::Metric,
+        Nature::Age,
+        Scope::Process,
+        Unit::Seconds,
+        "number of seconds since the last UP<->DOWN transition",
+    ),
+    FieldMetadata::new(
+        "downtime",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::Seconds,
+        "total downtime (in seconds)",
+    ),
+    FieldMetadata::new(
+        "qlimit",
+        Origin::Config,
+        Nature::Limit,
+        Scope::Service,
+        Unit::None,
+        "configured maxqueue for the server, or nothing if the value is 0",
+    ),
+    FieldMetadata::new(
+        "pid",
+        Origin::Key,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "process id (0 for first instance, 1 for second, ...)",
+    ),
+    FieldMetadata::new(
+        "iid",
+        Origin::Key,
+        Nature::Gauge,
+        Scope::Service,
+        Unit::None,
+        "unique proxy id",
+    ),
+    FieldMetadata::new(
+        "sid",
+        Origin::Key,
+        Nature::Gauge,
+        Scope::Service,
+        Unit::None,
+        "server id (unique inside a proxy)",
+    ),
+    FieldMetadata::new(
+        "throttle",
+        Origin::Metric,
+        Nature::Avg,
+        Scope::Process,
+        Unit::None,
+        "current throttle percentage for the server, when slowstart is active",
+    ),
+    FieldMetadata::new(
+        "lbtot",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of times a server was selected",
+    ),
+    FieldMetadata::new(
+        "tracked",
+        Origin::Key,
+        Nature::Name,
+        Scope::Service,
+        Unit::None,
+        "id of proxy/server if tracking is enabled",
+    ),
+    FieldMetadata::new(
+        "type",
+        Origin::Config,
+        Nature::Gauge,
+        Scope::Service,
+        Unit::None,
+        "(0=frontend, 1=backend, 2=server, 3=socket/listener)",
+    ),
+    FieldMetadata::new(
+        "rate",
+        Origin::Metric,
+        Nature::Rate,
+        Scope::Process,
+        Unit::PerSecond,
+        "number of sessions per second over last elapsed second",
+    ),
+    FieldMetadata::new(
+        "rate_lim",
+        Origin::Config,
+        Nature::Limit,
+        Scope::Process,
+        Unit::PerSecond,
+        "configured limit on new sessions per second",
+    ),
+    FieldMetadata::new(
+        "rate_max",
+        Origin::Metric,
+        Nature::Max,
+        Scope::Process,
+        Unit::PerSecond,
+        "max number of new sessions per second",
+    ),
+    FieldMetadata::new(
+        "check_status",
+        Origin::Status,
+        Nature::Output,
+        Scope::Process,
+        Unit::None,
+        "status of last health check",
+    ),
+    FieldMetadata::new(
+        "check_code",
+        Origin::Metric,
+        Nature::Output,
+        Scope::Process,
+        Unit::None,
+        "layer5-7 code, if available",
+    ),
+    FieldMetadata::new(
+        "check_duration",
+        Origin::Metric,
+        Nature::Duration,
+        Scope::Process,
+        Unit::Milliseconds,
+        "time in ms took to finish last health check",
+    ),
+    FieldMetadata::new(
+        "hrsp_1xx",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "http responses with 1xx code",
+    ),
+    FieldMetadata::new(
+        "hrsp_2xx",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "http responses with 2xx code",
+    ),
+    FieldMetadata::new(
+        "hrsp_3xx",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "http responses with 3xx code",
+    ),
+    FieldMetadata::new(
+        "hrsp_4xx",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "http responses with 4xx code",
+    ),
+    FieldMetadata::new(
+        "hrsp_5xx",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "http responses with 5xx code",
+    ),
+    FieldMetadata::new(
+        "hrsp_other",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "http responses with other codes (protocol error)",
+    ),
+    FieldMetadata::new(
+        "hanafail",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "failed health checks details",
+    ),
+    FieldMetadata::new(
+        "req_rate",
+        Origin::Metric,
+        Nature::Rate,
+        Scope::Process,
+        Unit::PerSecond,
+        "HTTP requests per second over last elapsed second",
+    ),
+    FieldMetadata::new(
+        "req_rate_max",
+        Origin::Metric,
+        Nature::Max,
+        Scope::Process,
+        Unit::PerSecond,
+        "max number of HTTP requests per second observed",
+    ),
+    FieldMetadata::new(
+        "req_tot",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of HTTP requests received",
+    ),
+    FieldMetadata::new(
+        "cli_abrt",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "number of data transfers aborted by the client",
+    ),
+    FieldMetadata::new(
+        "srv_abrt",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "number of data transfers aborted by the server",
+    ),
+    FieldMetadata::new(
+        "comp_in",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::Bytes,
+        "number of HTTP response bytes fed to the compressor",
+    ),
+    FieldMetadata::new(
+        "comp_out",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::Bytes,
+        "number of HTTP response bytes emitted by the compressor",
+    ),
+    FieldMetadata::new(
+        "comp_byp",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::Bytes,
+        "number of bytes that bypassed the HTTP compressor",
+    ),
+    FieldMetadata::new(
+        "comp_rsp",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "number of HTTP responses that were compressed",
+    ),
+    FieldMetadata::new(
+        "lastsess",
+        Origin::Metric,
+        Nature::Age,
+        Scope::Process,
+        Unit::Seconds,
+        "number of seconds since last session assigned to server/backend",
+    ),
+    FieldMetadata::new(
+        "qtime",
+        Origin::Metric,
+        Nature::Avg,
+        Scope::Process,
+        Unit::Milliseconds,
+        "the average queue time in ms over the 1024 last requests",
+    ),
+    FieldMetadata::new(
+        "ctime",
+        Origin::Metric,
+        Nature::Avg,
+        Scope::Process,
+        Unit::Milliseconds,
+        "the average connect time in ms over the 1024 last requests",
+    ),
+    FieldMetadata::new(
+        "rtime",
+        Origin::Metric,
+        Nature::Avg,
+        Scope::Process,
+        Unit::Milliseconds,
+        "the average response time in ms over the 1024 last requests",
+    ),
+    FieldMetadata::new(
+        "ttime",
+        Origin::Metric,
+        Nature::Avg,
+        Scope::Process,
+        Unit::Milliseconds,
+        "the average total session time in ms over the 1024 last requests",
+    ),
+    FieldMetadata::new(
+        "addr",
+        Origin::Config,
+        Nature::Gauge,
+        Scope::Service,
+        Unit::None,
+        "address:port or \"unix\". IPv6 has brackets around the address",
+    ),
+    FieldMetadata::new(
+        "mode",
+        Origin::Config,
+        Nature::Gauge,
+        Scope::Service,
+        Unit::None,
+        "proxy mode (tcp, http, health, unknown)",
+    ),
+    FieldMetadata::new(
+        "algo",
+        Origin::Config,
+        Nature::Gauge,
+        Scope::Service,
+        Unit::None,
+        "load balancing algorithm",
+    ),
+    FieldMetadata::new(
+        "conn_rate",
+        Origin::Metric,
+        Nature::Rate,
+        Scope::Process,
+        Unit::PerSecond,
+        "number of connections over the last elapsed second",
+    ),
+    FieldMetadata::new(
+        "conn_rate_max",
+        Origin::Metric,
+        Nature::Max,
+        Scope::Process,
+        Unit::PerSecond,
+        "highest known conn_rate",
+    ),
+    FieldMetadata::new(
+        "conn_tot",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "cumulative number of connections",
+    ),
+    FieldMetadata::new(
+        "intercepted",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "cum. number of intercepted requests",
+    ),
+    FieldMetadata::new(
+        "dcon",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "requests denied by 'tcp-request connection' rules",
+    ),
+    FieldMetadata::new(
+        "dses",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "requests denied by 'tcp-request session' rules",
+    ),
+    FieldMetadata::new(
+        "wrew",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "cumulative number of failed header rewriting warnings",
+    ),
+    FieldMetadata::new(
+        "connect",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "cumulative number of connection establishment attempts",
+    ),
+    FieldMetadata::new(
+        "reuse",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "cumulative number of connection reuses",
+    ),
+    FieldMetadata::new(
+        "cache_lookups",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "cumulative number of cache lookups",
+    ),
+    FieldMetadata::new(
+        "cache_hits",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "cumulative number of cache hits",
+    ),
+    FieldMetadata::new(
+        "srv_icur",
+        Origin::Metric,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "current number of idle connections available for reuse",
+    ),
+    FieldMetadata::new(
+        "src_ilim",
+        Origin::Config,
+        Nature::Limit,
+        Scope::Process,
+        Unit::None,
+        "limit on the number of available idle connections",
+    ),
+    FieldMetadata::new(
+        "qtime_max",
+        Origin::Metric,
+        Nature::Max,
+        Scope::Process,
+        Unit::Milliseconds,
+        "the maximum observed queue time in ms",
+    ),
+    FieldMetadata::new(
+        "ctime_max",
+        Origin::Metric,
+        Nature::Max,
+        Scope::Process,
+        Unit::Milliseconds,
+        "the maximum observed connect time in ms",
+    ),
+    FieldMetadata::new(
+        "rtime_max",
+        Origin::Metric,
+        Nature::Max,
+        Scope::Process,
+        Unit::Milliseconds,
+        "the maximum observed response time in ms",
+    ),
+    FieldMetadata::new(
+        "ttime_max",
+        Origin::Metric,
+        Nature::Max,
+        Scope::Process,
+        Unit::Milliseconds,
+        "the maximum observed total session time in ms",
+    ),
+    FieldMetadata::new(
+        "eint",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "cumulative number of internal errors",
+    ),
+    FieldMetadata::new(
+        "idle_conn_cur",
+        Origin::Metric,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "current number of unsafe idle connections",
+    ),
+    FieldMetadata::new(
+        "safe_conn_cur",
+        Origin::Metric,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "current number of safe idle connections",
+    ),
+    FieldMetadata::new(
+        "used_conn_cur",
+        Origin::Metric,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "current number of connections in use",
+    ),
+    FieldMetadata::new(
+        "need_conn_est",
+        Origin::Metric,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "estimated needed number of connections",
+    ),
+    FieldMetadata::new(
+        "uweight",
+        Origin::Metric,
+        Nature::Avg,
+        Scope::Process,
+        Unit::None,
+        "total user weight (backend), server user weight (server)",
+    ),
+    FieldMetadata::new(
+        "agg_server_check_status",
+        Origin::Metric,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "backend's aggregated gauge of servers' status",
+    ),
+    FieldMetadata::new(
+        "ssl_sess",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of ssl sessions established",
+    ),
+    FieldMetadata::new(
+        "ssl_reused_sess",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of ssl sessions reused",
+    ),
+    FieldMetadata::new(
+        "ssl_failed_handshake",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of failed handshakes",
+    ),
+    FieldMetadata::new(
+        "h2_headers_rcvd",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of received HEADERS frames",
+    ),
+    FieldMetadata::new(
+        "h2_data_rcvd",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of received DATA frames",
+    ),
+    FieldMetadata::new(
+        "h2_settings_rcvd",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of received SETTINGS frames",
+    ),
+    FieldMetadata::new(
+        "h2_rst_stream_rcvd",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of received RST_STREAM frames",
+    ),
+    FieldMetadata::new(
+        "h2_goaway_rcvd",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of received GOAWAY frames",
+    ),
+    FieldMetadata::new(
+        "h2_detected_conn_protocol_errors",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of connection protocol errors",
+    ),
+    FieldMetadata::new(
+        "h2_detected_strm_protocol_errors",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of stream protocol errors",
+    ),
+    FieldMetadata::new(
+        "h2_rst_stream_resp",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of RST_STREAM sent on detected error",
+    ),
+    FieldMetadata::new(
+        "h2_goaway_resp",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of GOAWAY sent on detected error",
+    ),
+    FieldMetadata::new(
+        "h2_open_connections",
+        Origin::Metric,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "count of currently open connections",
+    ),
+    FieldMetadata::new(
+        "h2_backend_open_streams",
+        Origin::Metric,
+        Nature::Gauge,
+        Scope::Process,
+        Unit::None,
+        "count of currently open streams",
+    ),
+    FieldMetadata::new(
+        "h2_total_connections",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of connections",
+    ),
+    FieldMetadata::new(
+        "h2_backend_total_streams",
+        Origin::Metric,
+        Nature::Counter,
+        Scope::Process,
+        Unit::None,
+        "total number of streams",
+    ),
+];
+
+pub fn stat_field(name: &str) -> Option<&'static FieldMetadata> {
+    static INDEX: OnceLock<HashMap<&'static str, &'static FieldMetadata>> = OnceLock::new();
+
+    INDEX
+        .get_or_init(|| STAT_FIELDS.iter().map(|x| (x.name, x)).collect())
+        .get(name)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::stat::JsonOutput;
+
+    #[test]
+    fn test_stat_fields_match_json_tags() {
+        let bytes = include_bytes!("../tests/files/2_5_5_show_stat.json");
+
+        let output = serde_json::from_slice::<JsonOutput>(bytes).unwrap();
+
+        for item in output.0.iter().flatten() {
+            let metadata = stat_field(&item.field.name)
+                .unwrap_or_else(|| panic!("{} has no metadata", item.field.name));
+            assert_eq!(
+                metadata.kind(),
+                MetricKind::from_tags(&item.tags),
+                "{}",
+                metadata.name
+            );
+            assert_eq!(metadata.scope, item.tags.scope, "{}", metadata.name);
+        }
+
+        assert_eq!(
+            stat_field("stot").unwrap().kind(),
+            Some(MetricKind::Counter)
+        );
+        assert_eq!(stat_field("scur").unwrap().kind(), Some(MetricKind::Gauge));
+        assert_eq!(stat_field("pxname").unwrap().kind(), None);
+        assert_eq!(stat_field("bin").unwrap().unit, Unit::Bytes);
+        assert!(stat_field("foo").is_none());
+    }
+}
@@ -0,0 +1,77 @@
+use core::{fmt, str::FromStr};
+
+use crate::command::Command;
+
+//
+/// Mode of a CLI session unlocking more commands, only at the admin level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CliMode {
+    /// `expert-mode on`, since 2.1, e.g. for `debug dev`.
+    Expert,
+    /// `experimental-mode on`, since 2.4, e.g. for `add server` on 2.4.
+    Experimental,
+}
+
+impl CliMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Expert => "expert-mode",
+            Self::Experimental => "experimental-mode",
+        }
+    }
+
+    /// Turns it on for the rest of the session.
+    pub fn to_command(&self) -> Command {
+        match self {
+            Self::Expert => Command::expert_mode(true),
+            Self::Experimental => Command::experimental_mode(true),
+        }
+    }
+}
+
+impl FromStr for CliMode {
+    type Err = CliModeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "expert-mode" => Ok(Self::Expert),
+            "experimental-mode" => Ok(Self::Experimental),
+            s => Err(CliModeParseError::Unknown(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for CliMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum CliModeParseError {
+    Unknown(Box<str>),
+}
+
+impl fmt::Display for CliModeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for CliModeParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_mode() {
+        assert_eq!("expert-mode".parse::<CliMode>().unwrap(), CliMode::Expert);
+        assert_eq!(
+            CliMode::Experimental.to_command().as_str(),
+            "experimental-mode on"
+        );
+        assert!("debug-mode".parse::<CliMode>().is_err());
+    }
+}
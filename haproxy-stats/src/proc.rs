@@ -0,0 +1,301 @@
+use core::{fmt, num::ParseIntError, time::Duration};
+use std::io::{BufRead as _, Cursor, Error as IoError};
+
+//
+const HEADER_PREFIX: &str = "#<PID>";
+const SECTION_PREFIX: char = '#';
+
+const COLUMN_PID: &str = "PID";
+const COLUMN_TYPE: &str = "type";
+const COLUMN_RELATIVE_PID: &str = "relative PID";
+const COLUMN_RELOADS: &str = "reloads";
+const COLUMN_UPTIME: &str = "uptime";
+const COLUMN_VERSION: &str = "version";
+
+const FAILED_PREFIX: &str = "[failed:";
+const WAS_PREFIX: &str = "[was:";
+
+//
+/// Output of `show proc` on the master CLI.
+#[derive(Debug, Clone, Default)]
+pub struct Processes {
+    pub master: Option<Process>,
+    pub workers: Vec<Process>,
+    /// Workers from before a reload, still serving their last connections.
+    pub old_workers: Vec<Process>,
+    pub programs: Vec<Process>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Process {
+    pub pid: u32,
+    pub r#type: ProcessType,
+    /// Only before 2.5, `[was: N]` for the old workers.
+    pub relative_pid: Option<u32>,
+    pub reloads: u64,
+    /// Only for the master, since 2.5.
+    pub failed_reloads: Option<u64>,
+    pub uptime: Duration,
+    pub version: Box<str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProcessType {
+    Master,
+    Worker,
+    Program,
+    Other(Box<str>),
+}
+
+impl From<&str> for ProcessType {
+    fn from(s: &str) -> Self {
+        match s {
+            "master" => Self::Master,
+            "worker" => Self::Worker,
+            "program" => Self::Program,
+            s => Self::Other(s.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Section {
+    Master,
+    Workers,
+    OldWorkers,
+    Programs,
+    Other,
+}
+
+impl Processes {
+    pub fn from_table_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, ProcessesFromTableBytesError> {
+        let bytes = bytes.as_ref();
+
+        let cursor = Cursor::new(bytes);
+
+        let mut columns: Option<Vec<Box<str>>> = None;
+        let mut section = Section::Master;
+        let mut processes = Self::default();
+
+        for line in cursor.lines() {
+            let line = line.map_err(ProcessesFromTableBytesError::LinesReadFailed)?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if line.starts_with(HEADER_PREFIX) {
+                columns = Some(parse_header(line));
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix(SECTION_PREFIX) {
+                section = match name.trim() {
+                    "workers" => Section::Workers,
+                    "old workers" => Section::OldWorkers,
+                    "programs" => Section::Programs,
+                    _ => Section::Other,
+                };
+                continue;
+            }
+
+            let columns = columns
+                .as_ref()
+                .ok_or(ProcessesFromTableBytesError::HeaderMissing)?;
+
+            let process = parse_row(line, columns)
+                .ok_or_else(|| ProcessesFromTableBytesError::LineInvalid(line.into()))?;
+
+            match section {
+                Section::Master => processes.master = Some(process),
+                Section::Workers => processes.workers.push(process),
+                Section::OldWorkers => processes.old_workers.push(process),
+                Section::Programs => processes.programs.push(process),
+                Section::Other => {}
+            }
+        }
+
+        if columns.is_none() {
+            return Err(ProcessesFromTableBytesError::HeaderMissing);
+        }
+
+        Ok(processes)
+    }
+
+    pub fn worker_pids(&self) -> Vec<u32> {
+        self.workers.iter().map(|x| x.pid).collect()
+    }
+
+    pub fn old_worker_pids(&self) -> Vec<u32> {
+        self.old_workers.iter().map(|x| x.pid).collect()
+    }
+}
+
+// e.g. `#<PID>          <type>          <relative PID>  <reloads>`
+fn parse_header(line: &str) -> Vec<Box<str>> {
+    line.split('<')
+        .skip(1)
+        .filter_map(|x| x.split_once('>'))
+        .map(|(name, _)| name.into())
+        .collect()
+}
+
+// Cells are separated by spaces, but `[failed: 0]` and `[was: 1]` contain spaces too.
+fn split_row(line: &str) -> Vec<String> {
+    let mut cells: Vec<String> = vec![];
+    let mut in_bracket = false;
+    for token in line.split_whitespace() {
+        if in_bracket || (token.starts_with(FAILED_PREFIX) && !cells.is_empty()) {
+            let cell = cells.last_mut().expect("");
+            cell.push(' ');
+            cell.push_str(token);
+        } else {
+            cells.push(token.to_owned());
+        }
+
+        if token.starts_with('[') {
+            in_bracket = true;
+        }
+        if token.ends_with(']') {
+            in_bracket = false;
+        }
+    }
+    cells
+}
+
+fn parse_row(line: &str, columns: &[Box<str>]) -> Option<Process> {
+    let cells = split_row(line);
+    if cells.len() != columns.len() {
+        return None;
+    }
+
+    let cell = |name: &str| -> Option<&str> {
+        columns
+            .iter()
+            .position(|x| x.as_ref() == name)
+            .map(|i| cells[i].as_str())
+    };
+
+    let (reloads, failed_reloads) = parse_reloads(cell(COLUMN_RELOADS)?)?;
+
+    Some(Process {
+        pid: cell(COLUMN_PID)?.parse().ok()?,
+        r#type: cell(COLUMN_TYPE)?.into(),
+        relative_pid: cell(COLUMN_RELATIVE_PID).and_then(parse_relative_pid),
+        reloads,
+        failed_reloads,
+        uptime: parse_uptime(cell(COLUMN_UPTIME)?).ok()?,
+        version: cell(COLUMN_VERSION)?.into(),
+    })
+}
+
+// e.g. `0` or `1 [failed: 0]`
+fn parse_reloads(s: &str) -> Option<(u64, Option<u64>)> {
+    match s.split_once(' ') {
+        Some((reloads, failed)) => {
+            let failed = failed
+                .strip_prefix(FAILED_PREFIX)?
+                .strip_suffix(']')?
+                .trim()
+                .parse()
+                .ok()?;
+            Some((reloads.parse().ok()?, Some(failed)))
+        }
+        None => Some((s.parse().ok()?, None)),
+    }
+}
+
+// e.g. `1`, `[was: 1]` or `N/A`
+fn parse_relative_pid(s: &str) -> Option<u32> {
+    match s.strip_prefix(WAS_PREFIX) {
+        Some(s) => s.strip_suffix(']')?.trim().parse().ok(),
+        None => s.parse().ok(),
+    }
+}
+
+// e.g. `0d00h02m31s`
+fn parse_uptime(s: &str) -> Result<Duration, ParseIntError> {
+    let mut secs = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        let unit = match c {
+            'd' => 86400,
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => continue,
+        };
+        secs += s[start..i].parse::<u64>()? * unit;
+        start = i + 1;
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+//
+#[derive(Debug)]
+pub enum ProcessesFromTableBytesError {
+    LinesReadFailed(IoError),
+    HeaderMissing,
+    LineInvalid(Box<str>),
+}
+
+impl fmt::Display for ProcessesFromTableBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for ProcessesFromTableBytesError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_processes_from_table_bytes() {
+        let bytes = include_bytes!("../tests/files/2_5_5_show_proc.txt");
+
+        let processes = Processes::from_table_bytes(bytes).unwrap();
+
+        let master = processes.master.as_ref().unwrap();
+        assert_eq!(master.pid, 1);
+        assert_eq!(master.r#type, ProcessType::Master);
+        assert_eq!(master.reloads, 1);
+        assert_eq!(master.failed_reloads, Some(0));
+        assert_eq!(master.uptime, Duration::from_secs(151));
+        assert_eq!(master.version, "2.5.5-384c5c5".into());
+
+        assert_eq!(processes.workers.len(), 1);
+        assert_eq!(processes.workers[0].pid, 23);
+        assert_eq!(processes.workers[0].r#type, ProcessType::Worker);
+        assert_eq!(processes.workers[0].reloads, 0);
+        assert_eq!(processes.workers[0].failed_reloads, None);
+        assert_eq!(processes.old_worker_pids(), vec![8]);
+        assert!(processes.programs.is_empty());
+    }
+
+    #[test]
+    fn test_processes_from_table_bytes_with_relative_pid() {
+        let bytes = b"#<PID>          <type>          <relative PID>  <reloads>       <uptime>        <version>\n\
+            1               master          0               1               0d00h00m40s     2.3.19\n\
+            # workers\n\
+            10              worker          1               0               0d00h00m10s     2.3.19\n\
+            # old workers\n\
+            9               worker          [was: 1]        1               0d00h00m40s     2.3.19\n\
+            # programs\n\
+            \n";
+
+        let processes = Processes::from_table_bytes(bytes).unwrap();
+
+        assert_eq!(processes.master.unwrap().failed_reloads, None);
+        assert_eq!(processes.workers[0].relative_pid, Some(1));
+        assert_eq!(processes.old_workers[0].relative_pid, Some(1));
+        assert_eq!(processes.old_workers[0].reloads, 1);
+
+        //
+        match Processes::from_table_bytes(b"Unknown command.\n") {
+            Err(ProcessesFromTableBytesError::HeaderMissing) => {}
+            x => panic!("{:?}", x),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use core::fmt;
+
+//
+const SUCCESS_PREFIX: &str = "Success=";
+const SEPARATOR: &str = "--";
+
+//
+/// Response of `reload` on the master CLI, since 2.7.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReloadStatus {
+    pub success: bool,
+    pub startup_logs: Box<str>,
+}
+
+impl ReloadStatus {
+    // e.g. "Success=1\n--\n[NOTICE]   (1) : haproxy version is 2.7.0\n"
+    pub fn from_reload_bytes(
+        bytes: impl AsRef<[u8]>,
+    ) -> Result<Self, ReloadStatusFromReloadBytesError> {
+        let s = String::from_utf8_lossy(bytes.as_ref());
+
+        let (first, rest) = s.split_once('\n').unwrap_or((&s, ""));
+
+        let success = match first.trim().strip_prefix(SUCCESS_PREFIX) {
+            Some("1") => true,
+            Some("0") => false,
+            _ => return Err(ReloadStatusFromReloadBytesError::SuccessMissing),
+        };
+
+        let startup_logs = match rest.split_once('\n') {
+            Some((line, logs)) if line.trim() == SEPARATOR => logs,
+            _ if rest.trim() == SEPARATOR => "",
+            _ => rest,
+        };
+
+        Ok(Self {
+            success,
+            startup_logs: startup_logs.into(),
+        })
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum ReloadStatusFromReloadBytesError {
+    SuccessMissing,
+}
+
+impl fmt::Display for ReloadStatusFromReloadBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for ReloadStatusFromReloadBytesError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_status_from_reload_bytes() {
+        let status = ReloadStatus::from_reload_bytes(
+            b"Success=0\n--\n[ALERT]    (48) : config : parsing [/usr/local/etc/haproxy/haproxy.cfg:12] : unknown keyword 'foo'.\n",
+        )
+        .unwrap();
+        assert!(!status.success);
+        assert!(status.startup_logs.starts_with("[ALERT]"));
+
+        let status = ReloadStatus::from_reload_bytes(b"Success=1\n--\n").unwrap();
+        assert!(status.success);
+        assert_eq!(status.startup_logs, "".into());
+
+        match ReloadStatus::from_reload_bytes(b"") {
+            Err(ReloadStatusFromReloadBytesError::SuccessMissing) => {}
+            x => panic!("{:?}", x),
+        }
+    }
+}
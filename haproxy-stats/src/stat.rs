@@ -5,7 +5,10 @@ use serde::Deserialize;
 use serde_enum_str::Deserialize_enum_str;
 use serde_json::{Error as SerdeJsonError, Map, Value};
 
-use crate::formats::json;
+use crate::{
+    formats::json,
+    metadata::{self, FieldMetadata},
+};
 
 //
 pub const SVNAME_FRONTEND: &str = "FRONTEND";
@@ -53,6 +56,10 @@ impl Statistic {
             _ => None,
         }
     }
+
+    pub fn field_metadata(name: &str) -> Option<&'static FieldMetadata> {
+        metadata::stat_field(name)
+    }
 }
 
 //
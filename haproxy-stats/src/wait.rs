@@ -0,0 +1,75 @@
+use core::fmt;
+
+//
+const DONE: &str = "Done.";
+const EXPIRED: &str = "Wait delay expired.";
+
+//
+/// Response of `wait`, since 2.7.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WaitStatus {
+    /// The condition was met.
+    Done,
+    /// The delay expired first, expected for a plain delay.
+    Expired,
+    /// e.g. the server does not exist.
+    Failed(Box<str>),
+}
+
+impl WaitStatus {
+    // e.g. "Done.\n"
+    pub fn from_wait_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, WaitStatusFromWaitBytesError> {
+        let s = String::from_utf8_lossy(bytes.as_ref());
+
+        match s.trim() {
+            "" => Err(WaitStatusFromWaitBytesError::Empty),
+            DONE => Ok(Self::Done),
+            EXPIRED => Ok(Self::Expired),
+            s => Ok(Self::Failed(s.into())),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(self, Self::Done)
+    }
+}
+
+//
+#[derive(Debug)]
+pub enum WaitStatusFromWaitBytesError {
+    Empty,
+}
+
+impl fmt::Display for WaitStatusFromWaitBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for WaitStatusFromWaitBytesError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wait_status_from_wait_bytes() {
+        assert_eq!(
+            WaitStatus::from_wait_bytes(b"Done.\n").unwrap(),
+            WaitStatus::Done
+        );
+        assert_eq!(
+            WaitStatus::from_wait_bytes(b"Wait delay expired.\n").unwrap(),
+            WaitStatus::Expired
+        );
+        assert_eq!(
+            WaitStatus::from_wait_bytes(b"Unknown failure.\n").unwrap(),
+            WaitStatus::Failed("Unknown failure.".into())
+        );
+
+        match WaitStatus::from_wait_bytes(b"\n") {
+            Err(WaitStatusFromWaitBytesError::Empty) => {}
+            x => panic!("{:?}", x),
+        }
+    }
+}